@@ -0,0 +1,128 @@
+// Same colors as the render command: white for empty tiles followed by hues spread by the
+// golden ratio
+const colors = [[255, 255, 255]];
+for (let idx = 1; idx < 256; idx++) {
+  const hue = ((idx * 0.61803398875) % 1) * 6;
+  const saturation = 0.65;
+  const value = 0.9;
+  const chroma = value * saturation;
+  const x = chroma * (1 - Math.abs((hue % 2) - 1));
+  const rgb = [
+    [chroma, x, 0], [x, chroma, 0], [0, chroma, x],
+    [0, x, chroma], [x, 0, chroma], [chroma, 0, x],
+  ][Math.min(Math.floor(hue), 5)];
+  const offset = value - chroma;
+  colors.push(rgb.map((channel) => Math.round((channel + offset) * 255)));
+}
+
+const canvas = document.getElementById("board");
+const paletteSelect = document.getElementById("palette");
+const pauseButton = document.getElementById("pause");
+const status = document.getElementById("status");
+const stats = document.getElementById("stats");
+const inspection = document.getElementById("inspection");
+const board = document.createElement("canvas");
+
+let latest = null;
+let isPaused = false;
+
+function tileKey(snapshot, tile, palette) {
+  const plant = snapshot.plants[tile];
+  if (plant === 0) {
+    return 0;
+  }
+  switch (palette) {
+    case "plant":
+      return plant;
+    case "genome":
+      return snapshot.genomes[plant] + 1;
+    case "cell_kind":
+      return 1;
+    default:
+      return snapshot.founders[snapshot.genomes[plant]] + 1;
+  }
+}
+
+function draw(frame) {
+  const snapshot = frame.snapshot;
+  const columns = snapshot.x_size;
+  const rows = snapshot.y_size;
+  board.width = columns;
+  board.height = rows;
+  const context = board.getContext("2d");
+  const image = context.createImageData(columns, rows);
+  for (let x = 0; x < columns; x++) {
+    for (let y = 0; y < rows; y++) {
+      const key = tileKey(snapshot, x * rows + y, paletteSelect.value);
+      const color = colors[key === 0 ? 0 : 1 + ((key - 1) % 255)];
+      const idx = 4 * (y * columns + x);
+      image.data.set([...color, 255], idx);
+    }
+  }
+  context.putImageData(image, 0, 0);
+
+  const scale = Math.max(
+    1,
+    Math.floor(Math.min(window.innerWidth / columns, (window.innerHeight - 80) / rows)),
+  );
+  canvas.width = columns * scale;
+  canvas.height = rows * scale;
+  const view = canvas.getContext("2d");
+  view.imageSmoothingEnabled = false;
+  view.drawImage(board, 0, 0, canvas.width, canvas.height);
+
+  let text = `round ${snapshot.round} | plants ${frame.plants} | genomes ${frame.genomes}`;
+  if (frame.top_genome !== null) {
+    text += ` | top yield ${frame.top_yield} (genome ${frame.top_genome})`;
+  }
+  if (frame.finished) {
+    text += " | finished";
+  }
+  stats.textContent = text;
+}
+
+const source = new EventSource("events");
+source.onopen = () => {
+  status.textContent = "live";
+};
+source.onerror = () => {
+  status.textContent = "disconnected";
+};
+source.onmessage = (event) => {
+  latest = JSON.parse(event.data);
+  // The server ends the stream after the final frame, which would otherwise reconnect
+  if (latest.finished) {
+    source.close();
+    status.textContent = "finished";
+  }
+  if (!isPaused) {
+    draw(latest);
+  }
+};
+
+paletteSelect.onchange = () => {
+  if (latest !== null) {
+    draw(latest);
+  }
+};
+pauseButton.onclick = () => {
+  isPaused = !isPaused;
+  pauseButton.textContent = isPaused ? "resume" : "pause";
+  if (!isPaused && latest !== null) {
+    draw(latest);
+  }
+};
+
+// Reports describe the world as it is now, which can be ahead of a paused board
+canvas.onclick = async (event) => {
+  if (latest === null) {
+    return;
+  }
+  const snapshot = latest.snapshot;
+  const scale = canvas.width / snapshot.x_size;
+  const x = Math.floor(event.offsetX / scale);
+  const y = Math.floor(event.offsetY / scale);
+  const response = await fetch(`inspect?tile=${x * snapshot.y_size + y}`);
+  const text = await response.text();
+  inspection.textContent = response.ok ? JSON.stringify(JSON.parse(text), null, 2) : text;
+};
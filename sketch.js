@@ -3,6 +3,7 @@ let board_idx = 0;
 let columns;
 let rows;
 let isPaused = false;
+let colorByFounder = false;
 
 const colors = [
   "aliceblue", "antiquewhite", "aqua", "aquamarine", "azure", "beige",
@@ -64,6 +65,12 @@ function keyPressed() {
     }
   } else if (key === "l") {
     redraw();
+  } else if (key === "f") {
+    colorByFounder = !colorByFounder;
+    if (board_idx > 0) {
+      board_idx -= 1;
+    }
+    redraw();
   }
 }
 
@@ -71,7 +78,8 @@ function draw() {
   background(255);
   fill(0);
   text(board_idx, width - 50, 50);
-  let board = tile_snapshots[board_idx];
+  let snapshots = colorByFounder ? founder_snapshots : tile_snapshots;
+  let board = snapshots[board_idx];
   for ( let i = 0; i < columns; i++) {
     for ( let j = 0; j < rows; j++) {
       if (board[i][j] == 0) {
@@ -85,8 +93,46 @@ function draw() {
       rect(i * w, j * w, w-1, w-1);
     }
   }
+  if (colorByFounder) {
+    drawFounderLegend(board);
+  }
   board_idx++;
   if (board_idx >= tile_snapshots.length) {
     noLoop();
   }
 }
+
+
+function drawFounderLegend(board) {
+  let counts = {};
+  for (let column of board) {
+    for (let founder of column) {
+      if (founder != 0) {
+        counts[founder] = (counts[founder] || 0) + 1;
+      }
+    }
+  }
+  let founders = Object.keys(counts).sort((a, b) => counts[b] - counts[a]);
+  textSize(10);
+  textAlign(LEFT, CENTER);
+  founders.slice(0, 20).forEach((founder, idx) => {
+    fill(colors[(founder - 1) % colors.length]);
+    rect(width - 95, 80 + idx * 14, 10, 10);
+    fill(0);
+    text(founderText(founder), width - 80, 85 + idx * 14);
+  });
+  textSize(30);
+  textAlign(CENTER, CENTER);
+}
+
+function founderText(founder) {
+  let label = founder_labels[founder];
+  if (label === undefined) {
+    return "g" + (founder - 1);
+  }
+  let text = label.name || "g" + (founder - 1);
+  if (label.tags) {
+    text += " [" + label.tags.join(", ") + "]";
+  }
+  return text;
+}
@@ -1,20 +1,28 @@
+use crate::founder::Founder;
 use crate::genome::{Genome, GenomeKind};
+use crate::genome_stats::GenomeStats;
 use crate::genomes::GenomeId;
 use crate::grid::Grid;
+use crate::mutation::Mutation;
+use crate::plant_context::PlantContext;
 use crate::plants::PlantId;
 use crate::rand::Rng;
 use crate::tiles::TileId;
 use ahash::AHashMap;
-use getset::{CopyGetters, Getters};
-use serde::Serialize;
+use getset::{CopyGetters, Getters, MutGetters};
+use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 
-#[derive(Debug, Clone, CopyGetters, Getters, Serialize)]
+type ScoreMap = AHashMap<(PlantId, TileId, usize), (usize, Option<f32>)>;
+
+#[derive(Debug, Clone, CopyGetters, Getters, MutGetters, Serialize, Deserialize)]
 pub struct ActiveGenome {
+    #[getset(get_copy = "pub")]
     id: GenomeId,
     #[serde(flatten)]
     #[getset(get = "pub")]
     genome: GenomeKind,
+    #[getset(get_copy = "pub")]
     num_plants: usize,
     #[getset(get_copy = "pub")]
     max_yield: usize,
@@ -22,8 +30,14 @@ pub struct ActiveGenome {
     created_at: usize,
     #[getset(get_copy = "pub")]
     parent_genome_id: Option<GenomeId>,
+    #[serde(flatten)]
+    #[getset(get = "pub")]
+    founder: Founder,
+    #[serde(flatten)]
+    #[getset(get_copy = "pub", get_mut = "pub")]
+    stats: GenomeStats,
     #[serde(skip)]
-    score_map: RefCell<AHashMap<(PlantId, TileId, usize), (usize, Option<f32>)>>,
+    score_map: RefCell<ScoreMap>,
 }
 
 impl ActiveGenome {
@@ -32,6 +46,7 @@ impl ActiveGenome {
         genome: GenomeKind,
         parent_genome_id: Option<GenomeId>,
         created_at: usize,
+        founder: Founder,
     ) -> Self {
         Self {
             id,
@@ -40,16 +55,19 @@ impl ActiveGenome {
             max_yield: 0,
             created_at,
             parent_genome_id,
-            score_map: RefCell::new(AHashMap::new()),
+            founder,
+            stats: GenomeStats::default(),
+            score_map: RefCell::new(ScoreMap::new()),
         }
     }
 
-    pub fn mutate(&self, rng: &mut Rng) -> GenomeKind {
-        self.genome.mutate(rng)
+    pub fn mutate(&self, mutation: &Mutation, rng: &mut Rng) -> GenomeKind {
+        self.genome.mutate(mutation, rng)
     }
 
     pub fn increment(&mut self) -> usize {
         self.num_plants = self.num_plants.checked_add(1).unwrap();
+        self.stats.add_plant(self.num_plants);
         self.num_plants
     }
 
@@ -67,29 +85,19 @@ impl ActiveGenome {
         grid: &Grid,
         available_tiles: &[TileId],
         plant_id: PlantId,
-        points: usize,
+        context: &PlantContext,
         rng: &mut Rng,
     ) -> Option<TileId> {
-        if points == 0 {
+        if context.points() == 0 {
             return None;
         }
 
-        let scores = available_tiles
-            .iter()
-            .filter_map(|&tile_id| Some(tile_id).zip(self.score(plant_id, grid, tile_id, points)))
-            .collect::<Vec<_>>();
-
-        let max_score = scores
-            .iter()
-            .map(|(_, score)| *score)
-            .fold(f32::NEG_INFINITY, f32::max);
-
         let mut total_cumulative_score = 0.0;
-        let cumulative_scores = scores
+        let cumulative_scores = self
+            .tile_weights(grid, available_tiles, plant_id, context)
             .into_iter()
-            .map(|(tile_id, score)| {
-                let score_weight = self.genome.score_weight();
-                total_cumulative_score += (score_weight * (score - max_score)).exp();
+            .map(|(tile_id, _, weight)| {
+                total_cumulative_score += weight;
                 (tile_id, total_cumulative_score)
             })
             .collect::<Vec<_>>();
@@ -101,23 +109,56 @@ impl ActiveGenome {
             .map(|(tile_id, _)| tile_id)
     }
 
+    // Score and unnormalized selection weight of every tile the genome can score, a softmax over
+    // the scores sharpened by the score weight
+    pub fn tile_weights(
+        &self,
+        grid: &Grid,
+        available_tiles: &[TileId],
+        plant_id: PlantId,
+        context: &PlantContext,
+    ) -> Vec<(TileId, f32, f32)> {
+        let scores = available_tiles
+            .iter()
+            .filter_map(|&tile_id| Some(tile_id).zip(self.score(plant_id, grid, tile_id, context)))
+            .collect::<Vec<_>>();
+
+        let max_score = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        let score_weight = self.genome.score_weight();
+        scores
+            .into_iter()
+            .map(|(tile_id, score)| (tile_id, score, (score_weight * (score - max_score)).exp()))
+            .collect()
+    }
+
     pub fn score(
         &self,
         plant_id: PlantId,
         grid: &Grid,
         tile_id: TileId,
-        points: usize,
+        context: &PlantContext,
     ) -> Option<f32> {
+        // The rest of the context changes every round, so caching genomes that sense it would
+        // only grow the map without ever hitting it
+        if self.genome.senses_plant() {
+            return self.genome.score(plant_id, tile_id, context, grid);
+        }
+
         let nonce = grid.nonce(tile_id);
+        let key = (plant_id, tile_id, context.points());
         let mut score_map = self.score_map.borrow_mut();
-        if let Some(&(cached_nonce, cached_score)) = score_map.get(&(plant_id, tile_id, points)) {
+        if let Some(&(cached_nonce, cached_score)) = score_map.get(&key) {
             if cached_nonce == nonce {
                 return cached_score;
             }
         }
 
-        let score = self.genome.score(plant_id, tile_id, points, grid);
-        score_map.insert((plant_id, tile_id, points), (nonce, score));
+        let score = self.genome.score(plant_id, tile_id, context, grid);
+        score_map.insert(key, (nonce, score));
         score
     }
 }
@@ -1,5 +1,7 @@
 use crate::genomes::GenomeId;
 use crate::grid::Grid;
+use crate::morphology::Morphology;
+use crate::plant_context::PlantContext;
 use crate::plants::PlantId;
 use crate::simple_graph::{all_connected, components, SimpleGraph};
 use crate::tiles::TileId;
@@ -8,21 +10,29 @@ use getset::CopyGetters;
 
 #[derive(Debug, Clone, CopyGetters, Default)]
 pub struct ActivePlant {
+    #[allow(dead_code)]
     id: PlantId,
     #[get_copy = "pub"]
     genome_id: GenomeId,
+    #[get_copy = "pub"]
+    created_at: usize,
     cells: SimpleGraph,
 }
 
 impl ActivePlant {
-    pub fn new(id: PlantId, genome_id: GenomeId) -> Self {
+    pub fn new(id: PlantId, genome_id: GenomeId, created_at: usize) -> Self {
         ActivePlant {
             id,
             genome_id,
+            created_at,
             ..Default::default()
         }
     }
 
+    pub fn size(&self) -> usize {
+        self.cells.node_count()
+    }
+
     pub fn cell_tiles(&self) -> Vec<TileId> {
         self.cells.nodes().collect()
     }
@@ -66,21 +76,45 @@ impl ActivePlant {
         Vec::new()
     }
 
-    pub fn energy_points(&self, grid: &Grid) -> usize {
+    // Takes the yield from the caller, who usually needs it as well, as it scans the surface
+    pub fn energy_points(&self, energy_yield: usize, grid: &Grid) -> usize {
         let energy_usage = self.energy_usage(grid);
         if energy_usage == 0 {
             return 0;
         }
 
-        let energy_yield = self.energy_yield(grid);
         energy_yield / energy_usage
     }
 
+    pub fn context(&self, points: usize, round: usize, grid: &Grid) -> PlantContext {
+        PlantContext::new(
+            points,
+            self.size(),
+            round - self.created_at,
+            self.energy_yield(grid),
+            self.energy_usage(grid),
+        )
+    }
+
+    pub fn morphology(&self, grid: &Grid) -> Morphology {
+        let positions = self
+            .cells
+            .nodes()
+            .map(|tile_id| grid.position(tile_id))
+            .collect::<Vec<_>>();
+        Morphology::new(
+            &positions,
+            self.cells.perimeter(),
+            self.cells.degrees(),
+            grid,
+        )
+    }
+
     pub fn available_tiles(&self) -> Vec<TileId> {
         self.cells.all_unoccupied_neighbors()
     }
 
-    fn energy_usage(&self, grid: &Grid) -> usize {
+    pub fn energy_usage(&self, grid: &Grid) -> usize {
         self.cells
             .nodes()
             .map(|tile_id| {
@@ -90,7 +124,7 @@ impl ActivePlant {
             .sum()
     }
 
-    fn energy_yield(&self, grid: &Grid) -> usize {
+    pub fn energy_yield(&self, grid: &Grid) -> usize {
         self.cells
             .unoccupied_neighbors_iter()
             .filter(|(unoccupied_node_id, _)| grid.is_empty(*unoccupied_node_id))
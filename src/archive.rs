@@ -0,0 +1,182 @@
+use crate::fitness::Fitness;
+use crate::genome::GenomeKind;
+use crate::rand::Rng;
+use anyhow::{bail, Context, Result};
+use derive_more::Constructor;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+// The perimeter counts the distinct empty or foreign tiles bordering a plant, at most four per
+// cell
+const MAX_PERIMETER_RATIO: f64 = 4.0;
+
+// Bumped whenever the descriptors change meaning, as elites binned under another definition can
+// no longer be compared. Version 1 counts the perimeter in bordering tiles rather than edges.
+const VERSION: u32 = 1;
+
+fn default_bins() -> usize {
+    10
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ArchiveConfig {
+    path: PathBuf,
+    #[serde(default = "default_bins")]
+    bins: usize,
+    #[serde(default)]
+    fitness: Fitness,
+    #[serde(default)]
+    reseed: usize,
+}
+
+#[derive(Debug, Copy, Clone, Constructor, CopyGetters, Serialize, Deserialize)]
+#[getset(get_copy = "pub")]
+pub struct Descriptors {
+    compactness: f64,
+    perimeter_ratio: f64,
+    takeover_rate: f64,
+}
+
+impl Descriptors {
+    // Scales every descriptor to [0, 1]
+    fn normalized(&self) -> [f64; 3] {
+        [
+            self.compactness,
+            self.perimeter_ratio / MAX_PERIMETER_RATIO,
+            self.takeover_rate,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Constructor, CopyGetters, Getters, Serialize, Deserialize)]
+pub struct Elite {
+    #[getset(get_copy = "pub")]
+    fitness: f64,
+    #[serde(flatten)]
+    #[getset(get_copy = "pub")]
+    descriptors: Descriptors,
+    #[serde(flatten)]
+    #[getset(get = "pub")]
+    genome: GenomeKind,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ArchiveFile {
+    // Archives from before versioning have none
+    #[serde(default)]
+    version: u32,
+    #[serde(flatten)]
+    elites: BTreeMap<String, Elite>,
+}
+
+// MAP-Elites archive keeping the fittest genome seen in every cell of a grid over the behavior
+// descriptors. The file doubles as a genome library keyed by cell.
+#[derive(Debug, Clone, Getters)]
+pub struct Archive {
+    path: PathBuf,
+    bins: usize,
+    #[getset(get = "pub")]
+    fitness: Fitness,
+    reseed: usize,
+    elites: BTreeMap<String, Elite>,
+}
+
+impl Archive {
+    pub fn load(config: &ArchiveConfig) -> Result<Self> {
+        if config.bins == 0 {
+            bail!("Archive needs at least one bin per descriptor");
+        }
+        if config.fitness == Fitness::Pareto {
+            bail!("Pareto ranks cannot be compared across trials, pick a scalar archive fitness");
+        }
+
+        let path = &config.path;
+        let elites = if path.exists() {
+            let contents = std::fs::read_to_string(path)?;
+            let file: ArchiveFile = toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse archive {}", path.display()))?;
+            if file.version != VERSION {
+                bail!(
+                    "Archive {} has version {} but version {VERSION} is needed, as its \
+                     descriptors were measured differently. Move it aside to start a new one.",
+                    path.display(),
+                    file.version
+                );
+            }
+            file.elites
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self {
+            path: path.clone(),
+            bins: config.bins,
+            fitness: config.fitness,
+            reseed: config.reseed,
+            elites,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let toml = toml::to_string_pretty(&ArchiveFile {
+            version: VERSION,
+            elites: self.elites.clone(),
+        })?;
+        std::fs::write(&self.path, toml)?;
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.elites.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elites.is_empty()
+    }
+
+    // Keeps the elite if its cell is empty or it beats the current occupant
+    pub fn insert(&mut self, elite: Elite) -> bool {
+        let key = self.cell(&elite.descriptors);
+        match self.elites.get(&key) {
+            Some(current)
+                if self.fitness.compare(elite.fitness, current.fitness) != Ordering::Greater =>
+            {
+                false
+            }
+            _ => {
+                self.elites.insert(key, elite);
+                true
+            }
+        }
+    }
+
+    // Folds in the elites of an archive that was filled separately, such as on another island
+    pub fn merge(&mut self, other: Archive) {
+        other.elites.into_values().for_each(|elite| {
+            self.insert(elite);
+        });
+    }
+
+    // Elites drawn uniformly over the occupied cells, as many as configured for reseeding
+    pub fn reseed(&self, rng: &mut Rng) -> Vec<(String, GenomeKind)> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let elites = self.elites.iter().collect::<Vec<_>>();
+        (0..self.reseed)
+            .map(|_| {
+                let (key, elite) = elites[rng.uniform(elites.len())];
+                (key.clone(), elite.genome.clone())
+            })
+            .collect()
+    }
+
+    fn cell(&self, descriptors: &Descriptors) -> String {
+        let [a, b, c] = descriptors
+            .normalized()
+            .map(|value| ((value.clamp(0.0, 1.0) * self.bins as f64) as usize).min(self.bins - 1));
+        format!("cell_{a}_{b}_{c}")
+    }
+}
@@ -0,0 +1,72 @@
+use crate::entity::GreedyEntity;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Deserialize, Serialize)]
+pub struct BlobFn {
+    #[serde(rename = "e")]
+    blob_e: [f32; 12],
+    #[serde(rename = "s")]
+    blob_s: [f32; 12],
+    #[serde(rename = "o")]
+    blob_o: [f32; 12],
+}
+
+impl BlobFn {
+    pub const LEN: usize = 3 * 12;
+
+    pub fn from_fn(mut f: impl FnMut() -> f32) -> Self {
+        Self {
+            blob_e: std::array::from_fn(|_| f()),
+            blob_s: std::array::from_fn(|_| f()),
+            blob_o: std::array::from_fn(|_| f()),
+        }
+    }
+
+    pub fn translate(self, amount: f32) -> Self {
+        Self {
+            blob_e: self.blob_e.map(|value| value + amount),
+            blob_s: self.blob_s.map(|value| value + amount),
+            blob_o: self.blob_o.map(|value| value + amount),
+        }
+    }
+
+    pub fn scale(self, amount: f32) -> Self {
+        Self {
+            blob_e: self.blob_e.map(|value| value * amount),
+            blob_s: self.blob_s.map(|value| value * amount),
+            blob_o: self.blob_o.map(|value| value * amount),
+        }
+    }
+
+    pub fn mutate(&self, mut mutator: impl FnMut(f32) -> f32) -> Self {
+        Self {
+            blob_e: self.blob_e.map(&mut mutator),
+            blob_s: self.blob_s.map(&mut mutator),
+            blob_o: self.blob_o.map(&mut mutator),
+        }
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = f32> + '_ {
+        self.blob_e
+            .iter()
+            .chain(&self.blob_s)
+            .chain(&self.blob_o)
+            .copied()
+    }
+
+    pub fn min(&self) -> f32 {
+        self.values().fold(f32::INFINITY, f32::min)
+    }
+
+    pub fn max(&self) -> f32 {
+        self.values().fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    pub fn score(&self, idx: usize, entity: GreedyEntity) -> f32 {
+        match entity {
+            GreedyEntity::Empty => self.blob_e[idx],
+            GreedyEntity::MyCell(_) => self.blob_s[idx],
+            GreedyEntity::OtherCell(_) => self.blob_o[idx],
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use anyhow::{Context, Result};
+use config::File;
+use serde::de::DeserializeOwned;
+
+// Reads the file `name` with any extension the config crate knows, such as config.toml
+pub fn load<T: DeserializeOwned>(name: &str) -> Result<T> {
+    read(name, true)
+}
+
+// Like `load`, but a missing file counts as empty so that every setting takes its default
+pub fn load_optional<T: DeserializeOwned>(name: &str) -> Result<T> {
+    read(name, false)
+}
+
+fn read<T: DeserializeOwned>(name: &str, required: bool) -> Result<T> {
+    let config = config::Config::builder()
+        .add_source(File::with_name(name).required(required))
+        .build()?;
+    config
+        .try_deserialize()
+        .with_context(|| format!("Failed to deserialize {name} file"))
+}
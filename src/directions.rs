@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+// Follows the `Doublet` and `TripletI` ordering
+#[derive(Debug, Copy, Clone, Deserialize, Serialize)]
+pub struct Directions<T> {
+    up: T,
+    right: T,
+    down: T,
+    left: T,
+}
+
+impl<T> Directions<T> {
+    pub fn from_fn(mut f: impl FnMut() -> T) -> Self {
+        Self {
+            up: f(),
+            right: f(),
+            down: f(),
+            left: f(),
+        }
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Directions<U> {
+        Directions {
+            up: f(self.up),
+            right: f(self.right),
+            down: f(self.down),
+            left: f(self.left),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        [&self.up, &self.right, &self.down, &self.left].into_iter()
+    }
+
+    pub fn into_array(self) -> [T; 4] {
+        [self.up, self.right, self.down, self.left]
+    }
+
+    pub fn opposite(&self, idx: usize) -> &T {
+        [&self.down, &self.left, &self.up, &self.right][idx]
+    }
+}
+
+// Follows the `TripletL` ordering
+#[derive(Debug, Copy, Clone, Deserialize, Serialize)]
+pub struct Corners<T> {
+    up_right: T,
+    up_left: T,
+    right_down: T,
+    right_up: T,
+    down_left: T,
+    down_right: T,
+    left_up: T,
+    left_down: T,
+}
+
+impl<T> Corners<T> {
+    pub fn from_fn(mut f: impl FnMut() -> T) -> Self {
+        Self {
+            up_right: f(),
+            up_left: f(),
+            right_down: f(),
+            right_up: f(),
+            down_left: f(),
+            down_right: f(),
+            left_up: f(),
+            left_down: f(),
+        }
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> Corners<U> {
+        Corners {
+            up_right: f(self.up_right),
+            up_left: f(self.up_left),
+            right_down: f(self.right_down),
+            right_up: f(self.right_up),
+            down_left: f(self.down_left),
+            down_right: f(self.down_right),
+            left_up: f(self.left_up),
+            left_down: f(self.left_down),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        [
+            &self.up_right,
+            &self.up_left,
+            &self.right_down,
+            &self.right_up,
+            &self.down_left,
+            &self.down_right,
+            &self.left_up,
+            &self.left_down,
+        ]
+        .into_iter()
+    }
+
+    pub fn into_array(self) -> [T; 8] {
+        [
+            self.up_right,
+            self.up_left,
+            self.right_down,
+            self.right_up,
+            self.down_left,
+            self.down_right,
+            self.left_up,
+            self.left_down,
+        ]
+    }
+}
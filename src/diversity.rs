@@ -0,0 +1,117 @@
+use crate::genome::Genome;
+use crate::organisms::Organisms;
+use crate::rand::Rng;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+// Kinds with more pairs than this are estimated from a sample of them
+const MAX_PAIRS: usize = 10_000;
+
+#[derive(Debug, Serialize)]
+pub struct Diversity {
+    round: usize,
+    genomes: usize,
+    lineages: usize,
+    shannon: f64,
+    pairs: usize,
+    min_distance: f32,
+    p25_distance: f32,
+    median_distance: f32,
+    p75_distance: f32,
+    max_distance: f32,
+    mean_distance: f32,
+}
+
+impl Diversity {
+    pub fn new(organisms: &Organisms, round: usize) -> Self {
+        let active_genomes = organisms
+            .active_genomes()
+            .iter()
+            .map(|&genome_id| organisms.genome(genome_id))
+            .collect::<Vec<_>>();
+
+        let mut lineages = active_genomes
+            .iter()
+            .map(|active_genome| active_genome.founder().id())
+            .collect::<Vec<_>>();
+        lineages.sort();
+        lineages.dedup();
+
+        let total_plants = organisms.active_plants().len() as f64;
+        let shannon = -active_genomes
+            .iter()
+            .map(|active_genome| active_genome.num_plants() as f64 / total_plants)
+            .filter(|&p| p > 0.0)
+            .map(|p| p * p.ln())
+            .sum::<f64>();
+
+        // Only genomes of the same kind and size share a parameter space, such as neural genomes
+        // with the same hidden layer. The map is ordered so that sampling is reproducible.
+        let mut kinds = BTreeMap::new();
+        active_genomes.iter().for_each(|active_genome| {
+            let genome = active_genome.genome();
+            let parameters = genome.parameters();
+            kinds
+                .entry((genome.name(), parameters.len()))
+                .or_insert_with(Vec::new)
+                .push(parameters);
+        });
+        // Seeded by the round so that sampling never disturbs the simulation's own random state
+        let mut rng = Rng::from_seed(round as u64);
+        let distance = |a: &[f32], b: &[f32]| {
+            a.iter()
+                .zip(b)
+                .map(|(x, y)| (x - y) * (x - y))
+                .sum::<f32>()
+                .sqrt()
+        };
+        let mut distances = Vec::new();
+        for parameters in kinds.values() {
+            let n = parameters.len();
+            if n * n.saturating_sub(1) / 2 <= MAX_PAIRS {
+                for (i, a) in parameters.iter().enumerate() {
+                    distances.extend(parameters[i + 1..].iter().map(|b| distance(a, b)));
+                }
+            } else {
+                for _ in 0..MAX_PAIRS {
+                    let i = rng.uniform(n);
+                    let j = (i + 1 + rng.uniform(n - 1)) % n;
+                    distances.push(distance(&parameters[i], &parameters[j]));
+                }
+            }
+        }
+        distances.sort_by(f32::total_cmp);
+
+        let quantile = |q: f32| {
+            let idx = (q * (distances.len() - 1) as f32).round() as usize;
+            distances[idx]
+        };
+        let (min, p25, median, p75, max, mean) = if distances.is_empty() {
+            (0.0, 0.0, 0.0, 0.0, 0.0, 0.0)
+        } else {
+            let mean = distances.iter().sum::<f32>() / distances.len() as f32;
+            (
+                quantile(0.0),
+                quantile(0.25),
+                quantile(0.5),
+                quantile(0.75),
+                quantile(1.0),
+                mean,
+            )
+        };
+
+        Self {
+            round,
+            genomes: active_genomes.len(),
+            lineages: lineages.len(),
+            shannon,
+            pairs: distances.len(),
+            min_distance: min,
+            p25_distance: p25,
+            median_distance: median,
+            p75_distance: p75,
+            max_distance: max,
+            mean_distance: mean,
+        }
+    }
+}
@@ -18,6 +18,8 @@ pub struct DoubletFn {
 }
 
 impl DoubletFn {
+    pub const LEN: usize = 6;
+
     pub fn from_fn(mut f: impl FnMut() -> f32) -> Self {
         Self {
             doublet_ee: f(),
@@ -62,6 +64,17 @@ impl DoubletFn {
         }
     }
 
+    pub fn values(&self) -> [f32; Self::LEN] {
+        [
+            self.doublet_ee,
+            self.doublet_es,
+            self.doublet_eo,
+            self.doublet_oe,
+            self.doublet_os,
+            self.doublet_oo,
+        ]
+    }
+
     pub fn min(&self) -> f32 {
         self.doublet_ee
             .min(self.doublet_es)
@@ -1,7 +1,7 @@
 use derive_more::{IsVariant, Unwrap};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, IsVariant, Unwrap, Serialize)]
+#[derive(Debug, Copy, Clone, IsVariant, Unwrap, Serialize, Deserialize)]
 #[serde(tag = "type")]
 pub enum Either<L, D> {
     Living(L),
@@ -0,0 +1,152 @@
+use crate::active_genome::ActiveGenome;
+use crate::either::Either::{self, *};
+use crate::genomes::Genomes;
+use crate::inactive_genome::InactiveGenome;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+const NUM_OBJECTIVES: usize = 5;
+// Ranking stops after this many fronts, as each one is another pass over the genomes
+const MAX_FRONTS: usize = 32;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Fitness {
+    #[default]
+    MaxYield,
+    IntegratedYield,
+    Descendants,
+    Longevity,
+    Area,
+    Pareto,
+}
+
+impl Fitness {
+    // One value per genome indexed by genome id, precise enough to pick the best `n`
+    pub fn evaluate(&self, genomes: &Genomes, round: usize, n: usize) -> Vec<f64> {
+        self.evaluate_among(genomes, round, n, |_| true)
+    }
+
+    // Like `evaluate`, but only living genomes compete for the Pareto fronts
+    pub fn evaluate_living(&self, genomes: &Genomes, round: usize, n: usize) -> Vec<f64> {
+        self.evaluate_among(genomes, round, n, |genome| matches!(genome, Living(_)))
+    }
+
+    fn evaluate_among(
+        &self,
+        genomes: &Genomes,
+        round: usize,
+        n: usize,
+        candidate: impl Fn(&Either<ActiveGenome, InactiveGenome>) -> bool,
+    ) -> Vec<f64> {
+        let objectives = genomes
+            .into_iter()
+            .map(|genome| Self::objectives(genome, round))
+            .collect::<Vec<_>>();
+        match self {
+            Fitness::MaxYield => objectives.iter().map(|values| values[0]).collect(),
+            Fitness::IntegratedYield => objectives.iter().map(|values| values[1]).collect(),
+            Fitness::Descendants => objectives.iter().map(|values| values[2]).collect(),
+            Fitness::Longevity => objectives.iter().map(|values| values[3]).collect(),
+            Fitness::Area => objectives.iter().map(|values| values[4]).collect(),
+            Fitness::Pareto => {
+                let candidates = genomes
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(_, genome)| candidate(genome))
+                    .map(|(idx, _)| idx)
+                    .collect();
+                Self::pareto_ranks(&objectives, candidates, n)
+            }
+        }
+    }
+
+    // Orders values so that the fitter genome compares as greater
+    pub fn compare(&self, a: f64, b: f64) -> Ordering {
+        match self {
+            Fitness::Pareto => b.total_cmp(&a),
+            _ => a.total_cmp(&b),
+        }
+    }
+
+    fn objectives(
+        genome: &Either<ActiveGenome, InactiveGenome>,
+        round: usize,
+    ) -> [f64; NUM_OBJECTIVES] {
+        let (max_yield, stats, created_at, died_at) = match genome {
+            Living(active_genome) => (
+                active_genome.max_yield(),
+                active_genome.stats(),
+                active_genome.created_at(),
+                round,
+            ),
+            Dead(inactive_genome) => (
+                inactive_genome.max_yield(),
+                inactive_genome.stats(),
+                inactive_genome.created_at(),
+                inactive_genome.died_at(),
+            ),
+        };
+        [
+            max_yield as f64,
+            stats.integrated_yield() as f64,
+            stats.total_plants() as f64,
+            (died_at - created_at) as f64,
+            stats.cells_grown() as f64,
+        ]
+    }
+
+    // Peels off Pareto fronts of the candidates until at least `n` are ranked or `MAX_FRONTS`
+    // fronts are peeled, rank 0 being the front. Candidates beyond that share the next rank and
+    // all other genomes come last.
+    fn pareto_ranks(
+        objectives: &[[f64; NUM_OBJECTIVES]],
+        mut candidates: Vec<usize>,
+        n: usize,
+    ) -> Vec<f64> {
+        let dominates = |a: &[f64; NUM_OBJECTIVES], b: &[f64; NUM_OBJECTIVES]| {
+            a.iter().zip(b).all(|(x, y)| x >= y) && a.iter().zip(b).any(|(x, y)| x > y)
+        };
+
+        // In decreasing lexicographic order every genome comes after all genomes dominating it,
+        // so anything dominated is also dominated by a member of the front found so far
+        candidates.sort_by(|&a, &b| {
+            objectives[b]
+                .iter()
+                .zip(&objectives[a])
+                .map(|(x, y)| x.total_cmp(y))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let mut ranks = vec![f64::INFINITY; objectives.len()];
+        let mut remaining = candidates;
+        let mut rank = 0.0;
+        let mut num_ranked = 0;
+        let mut num_fronts = 0;
+        while num_ranked < n && num_fronts < MAX_FRONTS && !remaining.is_empty() {
+            let mut front = Vec::new();
+            let mut rest = Vec::new();
+            for i in remaining {
+                if front
+                    .iter()
+                    .any(|&j: &usize| dominates(&objectives[j], &objectives[i]))
+                {
+                    rest.push(i);
+                } else {
+                    front.push(i);
+                }
+            }
+            front.iter().for_each(|&i| ranks[i] = rank);
+            num_ranked += front.len();
+            num_fronts += 1;
+            remaining = rest;
+            rank += 1.0;
+        }
+        remaining.iter().for_each(|&i| ranks[i] = rank);
+        ranks
+            .into_iter()
+            .map(|value| if value.is_finite() { value } else { rank + 1.0 })
+            .collect()
+    }
+}
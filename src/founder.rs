@@ -0,0 +1,15 @@
+use crate::genomes::GenomeId;
+use crate::plant_label::PlantLabel;
+use derive_more::Constructor;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Constructor, CopyGetters, Getters, Serialize, Deserialize)]
+pub struct Founder {
+    #[serde(rename = "founder_id")]
+    #[getset(get_copy = "pub")]
+    id: GenomeId,
+    #[serde(flatten)]
+    #[getset(get = "pub")]
+    label: PlantLabel,
+}
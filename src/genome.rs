@@ -1,5 +1,7 @@
-use crate::genomes::{DoubletGenome, TripletGenome};
+use crate::genomes::{BlobGenome, DoubletGenome, NeuralGenome, OrientedGenome, TripletGenome};
 use crate::grid::Grid;
+use crate::mutation::Mutation;
+use crate::plant_context::PlantContext;
 use crate::plants::PlantId;
 use crate::rand::Rng;
 use crate::tiles::TileId;
@@ -7,18 +9,49 @@ use enum_dispatch::enum_dispatch;
 use serde::{Deserialize, Serialize};
 
 #[enum_dispatch]
+#[allow(clippy::enum_variant_names, clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum GenomeKind {
     DoubletGenome,
     TripletGenome,
+    NeuralGenome,
+    BlobGenome,
+    OrientedGenome,
+}
+
+impl GenomeKind {
+    // The kind as written in config and result files
+    pub fn name(&self) -> &'static str {
+        match self {
+            GenomeKind::DoubletGenome(_) => "doublet_genome",
+            GenomeKind::TripletGenome(_) => "triplet_genome",
+            GenomeKind::NeuralGenome(_) => "neural_genome",
+            GenomeKind::BlobGenome(_) => "blob_genome",
+            GenomeKind::OrientedGenome(_) => "oriented_genome",
+        }
+    }
 }
 
 #[enum_dispatch(GenomeKind)]
 pub trait Genome {
-    fn mutate(&self, rng: &mut Rng) -> GenomeKind;
+    fn mutate(&self, mutation: &Mutation, rng: &mut Rng) -> GenomeKind;
 
-    fn score(&self, plant_id: PlantId, tile_id: TileId, points: usize, grid: &Grid) -> Option<f32>;
+    fn score(
+        &self,
+        plant_id: PlantId,
+        tile_id: TileId,
+        context: &PlantContext,
+        grid: &Grid,
+    ) -> Option<f32>;
 
     fn score_weight(&self) -> f32;
+
+    fn parameters(&self) -> Vec<f32>;
+
+    // Genomes that ignore the plant only receive the energy points in their context, which keeps
+    // their cached scores valid as the plant grows
+    fn senses_plant(&self) -> bool {
+        false
+    }
 }
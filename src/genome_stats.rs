@@ -0,0 +1,53 @@
+use getset::CopyGetters;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Default, CopyGetters, Serialize, Deserialize)]
+#[getset(get_copy = "pub")]
+pub struct GenomeStats {
+    integrated_yield: usize,
+    total_plants: usize,
+    peak_plants: usize,
+    cells_grown: usize,
+    takeovers_performed: usize,
+    takeovers_suffered: usize,
+    dead_plants: usize,
+    average_plant_lifespan: f64,
+    peak_area: usize,
+    #[serde(skip)]
+    area: usize,
+}
+
+impl GenomeStats {
+    pub fn add_yield(&mut self, energy_yield: usize) {
+        self.integrated_yield = self.integrated_yield.checked_add(energy_yield).unwrap();
+    }
+
+    pub fn add_plant(&mut self, num_plants: usize) {
+        self.total_plants = self.total_plants.checked_add(1).unwrap();
+        self.peak_plants = std::cmp::max(self.peak_plants, num_plants);
+    }
+
+    pub fn remove_plant(&mut self, lifespan: usize) {
+        self.dead_plants = self.dead_plants.checked_add(1).unwrap();
+        let delta = lifespan as f64 - self.average_plant_lifespan;
+        self.average_plant_lifespan += delta / self.dead_plants as f64;
+    }
+
+    pub fn add_cell(&mut self) {
+        self.cells_grown = self.cells_grown.checked_add(1).unwrap();
+        self.area = self.area.checked_add(1).unwrap();
+        self.peak_area = std::cmp::max(self.peak_area, self.area);
+    }
+
+    pub fn remove_cells(&mut self, num_cells: usize) {
+        self.area = self.area.checked_sub(num_cells).unwrap();
+    }
+
+    pub fn add_takeover(&mut self) {
+        self.takeovers_performed = self.takeovers_performed.checked_add(1).unwrap();
+    }
+
+    pub fn add_loss(&mut self) {
+        self.takeovers_suffered = self.takeovers_suffered.checked_add(1).unwrap();
+    }
+}
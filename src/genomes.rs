@@ -1,13 +1,19 @@
+mod blob_genome;
 mod doublet_genome;
+mod neural_genome;
+mod oriented_genome;
 mod triplet_genome;
 
 use crate::active_genome::ActiveGenome;
 use crate::either::Either;
 use crate::inactive_genome::InactiveGenome;
 use derive_more::{Display, From, Into, IntoIterator};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
+pub use blob_genome::BlobGenome;
 pub use doublet_genome::DoubletGenome;
+pub use neural_genome::NeuralGenome;
+pub use oriented_genome::OrientedGenome;
 pub use triplet_genome::TripletGenome;
 
 #[derive(
@@ -24,6 +30,7 @@ pub use triplet_genome::TripletGenome;
     From,
     Into,
     Serialize,
+    Deserialize,
 )]
 pub struct GenomeId(usize);
 
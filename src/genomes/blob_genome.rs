@@ -0,0 +1,152 @@
+use crate::blob_fn::BlobFn;
+use crate::entity::GreedyEntity;
+use crate::genome::{Genome, GenomeKind};
+use crate::grid::Grid;
+use crate::mutation::{Mutation, ResetRange};
+use crate::plant_context::PlantContext;
+use crate::plants::PlantId;
+use crate::rand::Rng;
+use crate::singlet_fn::SingletFn;
+use crate::tiles::TileId;
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+struct Config {
+    score_weight: f32,
+    singlet: SingletFn,
+    blob: BlobFn,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    step_size: Option<f32>,
+}
+
+#[derive(Debug, Copy, Clone, Getters, Serialize, Deserialize)]
+#[serde(from = "Config", into = "Config")]
+pub struct BlobGenome {
+    score_weight: f32,
+
+    #[getset(get = "pub")]
+    singlet_fn: SingletFn,
+
+    #[getset(get = "pub")]
+    blob_fn: BlobFn,
+
+    step_size: Option<f32>,
+}
+
+impl From<Config> for BlobGenome {
+    fn from(config: Config) -> Self {
+        Self::new(
+            config.score_weight,
+            config.singlet,
+            config.blob,
+            config.step_size,
+        )
+    }
+}
+
+impl From<BlobGenome> for Config {
+    fn from(genome: BlobGenome) -> Self {
+        Config {
+            score_weight: genome.score_weight,
+            singlet: genome.singlet_fn,
+            blob: genome.blob_fn,
+            step_size: genome.step_size,
+        }
+    }
+}
+
+impl BlobGenome {
+    pub fn random(rng: &mut Rng) -> Self {
+        let score_weight = rng.norm() * 2.0;
+        let singlet_fn = SingletFn::from_fn(|| rng.norm() * 2.0);
+        let blob_fn = BlobFn::from_fn(|| rng.norm() * 2.0);
+        Self::new(score_weight, singlet_fn, blob_fn, None)
+    }
+
+    fn rescale(self) -> Self {
+        let min = self.singlet_fn.min().min(self.blob_fn.min());
+        let max = self.singlet_fn.max().max(self.blob_fn.max());
+        let scale = 1.0 / (max - min);
+        Self {
+            score_weight: self.score_weight.abs() / scale,
+            singlet_fn: self.singlet_fn.translate(-min).scale(scale),
+            blob_fn: self.blob_fn.translate(-min).scale(scale),
+            step_size: self.step_size,
+        }
+    }
+
+    fn new(
+        score_weight: f32,
+        singlet_fn: SingletFn,
+        blob_fn: BlobFn,
+        step_size: Option<f32>,
+    ) -> Self {
+        Self {
+            score_weight,
+            singlet_fn,
+            blob_fn,
+            step_size,
+        }
+        .rescale()
+    }
+}
+
+impl Genome for BlobGenome {
+    fn score_weight(&self) -> f32 {
+        self.score_weight
+    }
+
+    fn parameters(&self) -> Vec<f32> {
+        let mut parameters = Vec::with_capacity(SingletFn::LEN + BlobFn::LEN);
+        parameters.extend(self.singlet_fn.values());
+        parameters.extend(self.blob_fn.values());
+        parameters
+    }
+
+    fn mutate(&self, mutation: &Mutation, rng: &mut Rng) -> GenomeKind {
+        let score_weight = mutation.mutate_weight(self.score_weight, rng);
+        let step_size = mutation.adapt(self.step_size, rng);
+        let num_params = SingletFn::LEN + BlobFn::LEN;
+        let mut mutator = mutation.mutator(step_size, num_params, ResetRange::Unit, rng);
+        Self::new(
+            score_weight,
+            self.singlet_fn.mutate(&mut mutator),
+            self.blob_fn.mutate(&mut mutator),
+            step_size,
+        )
+        .into()
+    }
+
+    fn score(
+        &self,
+        plant_id: PlantId,
+        tile_id: TileId,
+        context: &PlantContext,
+        grid: &Grid,
+    ) -> Option<f32> {
+        let entity_1 = grid.entity(tile_id).into_greedy(plant_id);
+        match entity_1 {
+            GreedyEntity::Empty => {}
+            GreedyEntity::MyCell(_) => return None,
+            GreedyEntity::OtherCell(_) => {
+                if context.points() <= 1 {
+                    return None;
+                }
+            }
+        }
+
+        let mut score = 0.0;
+        score += self.singlet_fn().score(entity_1);
+        score +=
+            grid.blob(tile_id)
+                .tile_ids()
+                .iter()
+                .enumerate()
+                .fold(0.0, |sum, (idx, &tile_id_2)| {
+                    let entity_2 = grid.entity(tile_id_2).into_greedy(plant_id);
+                    sum + self.blob_fn().score(idx, entity_2)
+                });
+        Some(score)
+    }
+}
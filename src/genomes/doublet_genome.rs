@@ -2,6 +2,8 @@ use crate::doublet_fn::DoubletFn;
 use crate::entity::GreedyEntity;
 use crate::genome::{Genome, GenomeKind};
 use crate::grid::Grid;
+use crate::mutation::{Mutation, ResetRange};
+use crate::plant_context::PlantContext;
 use crate::plants::PlantId;
 use crate::rand::Rng;
 use crate::singlet_fn::SingletFn;
@@ -14,6 +16,8 @@ struct Config {
     score_weight: f32,
     singlet: SingletFn,
     doublet: DoubletFn,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    step_size: Option<f32>,
 }
 
 #[derive(Debug, Copy, Clone, Getters, Serialize, Deserialize)]
@@ -26,11 +30,18 @@ pub struct DoubletGenome {
 
     #[getset(get = "pub")]
     doublet_fn: DoubletFn,
+
+    step_size: Option<f32>,
 }
 
 impl From<Config> for DoubletGenome {
     fn from(config: Config) -> Self {
-        Self::new(config.score_weight, config.singlet, config.doublet)
+        Self::new(
+            config.score_weight,
+            config.singlet,
+            config.doublet,
+            config.step_size,
+        )
     }
 }
 
@@ -40,6 +51,7 @@ impl From<DoubletGenome> for Config {
             score_weight: genome.score_weight,
             singlet: genome.singlet_fn,
             doublet: genome.doublet_fn,
+            step_size: genome.step_size,
         }
     }
 }
@@ -49,7 +61,7 @@ impl DoubletGenome {
         let score_weight = rng.norm() * 2.0;
         let singlet_fn = SingletFn::from_fn(|| rng.norm() * 2.0);
         let doublet_fn = DoubletFn::from_fn(|| rng.norm() * 2.0);
-        Self::new(score_weight, singlet_fn, doublet_fn)
+        Self::new(score_weight, singlet_fn, doublet_fn, None)
     }
 
     fn rescale(self) -> Self {
@@ -60,14 +72,21 @@ impl DoubletGenome {
             score_weight: self.score_weight.abs() / scale,
             singlet_fn: self.singlet_fn.translate(-min).scale(scale),
             doublet_fn: self.doublet_fn.translate(-min).scale(scale),
+            step_size: self.step_size,
         }
     }
 
-    fn new(score_weight: f32, singlet_fn: SingletFn, doublet_fn: DoubletFn) -> Self {
+    fn new(
+        score_weight: f32,
+        singlet_fn: SingletFn,
+        doublet_fn: DoubletFn,
+        step_size: Option<f32>,
+    ) -> Self {
         Self {
             score_weight,
             singlet_fn,
             doublet_fn,
+            step_size,
         }
         .rescale()
     }
@@ -78,24 +97,40 @@ impl Genome for DoubletGenome {
         self.score_weight
     }
 
-    fn mutate(&self, rng: &mut Rng) -> GenomeKind {
-        let score_weight = self.score_weight + rng.norm() * 0.1 * self.score_weight;
-        let mut mutator = |value| value + rng.norm() * 0.01;
+    fn parameters(&self) -> Vec<f32> {
+        let mut parameters = Vec::with_capacity(SingletFn::LEN + DoubletFn::LEN);
+        parameters.extend(self.singlet_fn.values());
+        parameters.extend(self.doublet_fn.values());
+        parameters
+    }
+
+    fn mutate(&self, mutation: &Mutation, rng: &mut Rng) -> GenomeKind {
+        let score_weight = mutation.mutate_weight(self.score_weight, rng);
+        let step_size = mutation.adapt(self.step_size, rng);
+        let num_params = SingletFn::LEN + DoubletFn::LEN;
+        let mut mutator = mutation.mutator(step_size, num_params, ResetRange::Unit, rng);
         Self::new(
             score_weight,
             self.singlet_fn.mutate(&mut mutator),
             self.doublet_fn.mutate(&mut mutator),
+            step_size,
         )
         .into()
     }
 
-    fn score(&self, plant_id: PlantId, tile_id: TileId, points: usize, grid: &Grid) -> Option<f32> {
+    fn score(
+        &self,
+        plant_id: PlantId,
+        tile_id: TileId,
+        context: &PlantContext,
+        grid: &Grid,
+    ) -> Option<f32> {
         let entity_1 = grid.entity(tile_id).into_greedy(plant_id);
         match entity_1 {
             GreedyEntity::Empty => {}
             GreedyEntity::MyCell(_) => return None,
             GreedyEntity::OtherCell(_) => {
-                if points <= 1 {
+                if context.points() <= 1 {
                     return None;
                 }
             }
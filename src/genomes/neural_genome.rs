@@ -0,0 +1,157 @@
+use crate::entity::GreedyEntity;
+use crate::genome::{Genome, GenomeKind};
+use crate::grid::Grid;
+use crate::mlp::Mlp;
+use crate::mutation::{Mutation, ResetRange};
+use crate::plant_context::PlantContext;
+use crate::plants::PlantId;
+use crate::rand::Rng;
+use crate::tiles::TileId;
+use anyhow::bail;
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+
+const HIDDEN_SIZE: usize = 8;
+
+// Center tile, one-hot empty/mine/other for each of the 12 blob tiles, then the plant context
+// (energy points, size, age, yield and energy balance per cell)
+const NUM_FEATURES: usize = 1 + 3 * 12 + 5;
+
+// Genomes saved before age, yield and energy balance were inputs lack their three weights
+const LEGACY_NUM_FEATURES: usize = NUM_FEATURES - 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Config {
+    score_weight: f32,
+    mlp: Mlp,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    step_size: Option<f32>,
+}
+
+#[derive(Debug, Clone, Getters, Serialize, Deserialize)]
+#[serde(try_from = "Config", into = "Config")]
+pub struct NeuralGenome {
+    score_weight: f32,
+
+    #[getset(get = "pub")]
+    mlp: Mlp,
+
+    step_size: Option<f32>,
+}
+
+impl TryFrom<Config> for NeuralGenome {
+    type Error = anyhow::Error;
+
+    fn try_from(config: Config) -> Result<Self, Self::Error> {
+        let mlp = match config.mlp.input_size() {
+            NUM_FEATURES => config.mlp,
+            LEGACY_NUM_FEATURES => config.mlp.pad_inputs(NUM_FEATURES),
+            input_size => bail!("Neural genome expects {NUM_FEATURES} inputs but has {input_size}"),
+        };
+        Ok(Self::new(config.score_weight, mlp, config.step_size))
+    }
+}
+
+impl From<NeuralGenome> for Config {
+    fn from(genome: NeuralGenome) -> Self {
+        Config {
+            score_weight: genome.score_weight,
+            mlp: genome.mlp,
+            step_size: genome.step_size,
+        }
+    }
+}
+
+impl NeuralGenome {
+    pub fn random(rng: &mut Rng) -> Self {
+        let score_weight = rng.norm() * 2.0;
+        let mlp = Mlp::from_fn(NUM_FEATURES, HIDDEN_SIZE, |fan_in| {
+            rng.norm() / (fan_in as f32).sqrt()
+        });
+        Self::new(score_weight, mlp, None)
+    }
+
+    fn new(score_weight: f32, mlp: Mlp, step_size: Option<f32>) -> Self {
+        Self {
+            score_weight: score_weight.abs(),
+            mlp,
+            step_size,
+        }
+    }
+
+    fn features(
+        &self,
+        plant_id: PlantId,
+        tile_id: TileId,
+        context: &PlantContext,
+        grid: &Grid,
+    ) -> [f32; NUM_FEATURES] {
+        let mut features = [0.0; NUM_FEATURES];
+        if let GreedyEntity::OtherCell(_) = grid.entity(tile_id).into_greedy(plant_id) {
+            features[0] = 1.0;
+        }
+        grid.blob(tile_id)
+            .tile_ids()
+            .iter()
+            .enumerate()
+            .for_each(|(idx, &blob_tile_id)| {
+                let offset = match grid.entity(blob_tile_id).into_greedy(plant_id) {
+                    GreedyEntity::Empty => 0,
+                    GreedyEntity::MyCell(_) => 1,
+                    GreedyEntity::OtherCell(_) => 2,
+                };
+                features[1 + 3 * idx + offset] = 1.0;
+            });
+        let size = context.size().max(1) as f32;
+        features[NUM_FEATURES - 5] = context.points() as f32;
+        features[NUM_FEATURES - 4] = size.ln_1p();
+        features[NUM_FEATURES - 3] = (context.age() as f32).ln_1p();
+        features[NUM_FEATURES - 2] = (context.energy_yield() as f32).ln_1p();
+        features[NUM_FEATURES - 1] = context.energy_balance() as f32 / size;
+        features
+    }
+}
+
+impl Genome for NeuralGenome {
+    fn score_weight(&self) -> f32 {
+        self.score_weight
+    }
+
+    fn senses_plant(&self) -> bool {
+        true
+    }
+
+    fn parameters(&self) -> Vec<f32> {
+        self.mlp.values().collect()
+    }
+
+    fn mutate(&self, mutation: &Mutation, rng: &mut Rng) -> GenomeKind {
+        let score_weight = mutation.mutate_weight(self.score_weight, rng);
+        let step_size = mutation.adapt(self.step_size, rng);
+        let num_params = self.mlp.num_params();
+        let mut mutator = mutation.mutator(step_size, num_params, ResetRange::Normal, rng);
+        Self::new(score_weight, self.mlp.mutate(&mut mutator), step_size).into()
+    }
+
+    fn score(
+        &self,
+        plant_id: PlantId,
+        tile_id: TileId,
+        context: &PlantContext,
+        grid: &Grid,
+    ) -> Option<f32> {
+        let entity_1 = grid.entity(tile_id).into_greedy(plant_id);
+        match entity_1 {
+            GreedyEntity::Empty => {}
+            GreedyEntity::MyCell(_) => return None,
+            GreedyEntity::OtherCell(_) => {
+                if context.points() <= 1 {
+                    return None;
+                }
+            }
+        }
+
+        let features = self.features(plant_id, tile_id, context, grid);
+        Some(self.mlp.evaluate(&features))
+    }
+}
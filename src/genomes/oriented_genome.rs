@@ -0,0 +1,281 @@
+use crate::directions::{Corners, Directions};
+use crate::doublet_fn::DoubletFn;
+use crate::entity::GreedyEntity;
+use crate::genome::{Genome, GenomeKind};
+use crate::grid::Grid;
+use crate::mutation::{Mutation, ResetRange};
+use crate::plant_context::PlantContext;
+use crate::plants::PlantId;
+use crate::rand::Rng;
+use crate::singlet_fn::SingletFn;
+use crate::tiles::TileId;
+use crate::triplet_fn::TripletFn;
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+struct Config {
+    score_weight: f32,
+    singlet: SingletFn,
+    growth: Directions<f32>,
+    doublet: Directions<DoubletFn>,
+    triplet_l: Corners<TripletFn>,
+    triplet_i: Directions<TripletFn>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    step_size: Option<f32>,
+}
+
+#[derive(Debug, Copy, Clone, Getters, Serialize, Deserialize)]
+#[serde(from = "Config", into = "Config")]
+pub struct OrientedGenome {
+    score_weight: f32,
+
+    #[getset(get = "pub")]
+    singlet_fn: SingletFn,
+
+    // Bonus for growing away from an own cell in the given direction
+    #[getset(get = "pub")]
+    growth_fn: Directions<f32>,
+
+    #[getset(get = "pub")]
+    doublet_fns: Directions<DoubletFn>,
+
+    #[getset(get = "pub")]
+    triplet_l_fns: Corners<TripletFn>,
+
+    #[getset(get = "pub")]
+    triplet_i_fns: Directions<TripletFn>,
+
+    step_size: Option<f32>,
+}
+
+impl From<Config> for OrientedGenome {
+    fn from(config: Config) -> Self {
+        Self::new(
+            config.score_weight,
+            config.singlet,
+            config.growth,
+            config.doublet,
+            config.triplet_l,
+            config.triplet_i,
+            config.step_size,
+        )
+    }
+}
+
+impl From<OrientedGenome> for Config {
+    fn from(genome: OrientedGenome) -> Self {
+        Config {
+            score_weight: genome.score_weight,
+            singlet: genome.singlet_fn,
+            growth: genome.growth_fn,
+            doublet: genome.doublet_fns,
+            triplet_l: genome.triplet_l_fns,
+            triplet_i: genome.triplet_i_fns,
+            step_size: genome.step_size,
+        }
+    }
+}
+
+impl OrientedGenome {
+    const NUM_PARAMS: usize =
+        SingletFn::LEN + 4 + 4 * DoubletFn::LEN + 8 * TripletFn::LEN + 4 * TripletFn::LEN;
+
+    pub fn random(rng: &mut Rng) -> Self {
+        let score_weight = rng.norm() * 2.0;
+        let singlet_fn = SingletFn::from_fn(|| rng.norm() * 2.0);
+        let growth_fn = Directions::from_fn(|| rng.norm() * 2.0);
+        let doublet_fns = Directions::from_fn(|| DoubletFn::from_fn(|| rng.norm() * 2.0));
+        let triplet_l_fns = Corners::from_fn(|| TripletFn::from_fn(|| rng.norm() * 2.0));
+        let triplet_i_fns = Directions::from_fn(|| TripletFn::from_fn(|| rng.norm() * 2.0));
+        Self::new(
+            score_weight,
+            singlet_fn,
+            growth_fn,
+            doublet_fns,
+            triplet_l_fns,
+            triplet_i_fns,
+            None,
+        )
+    }
+
+    fn rescale(self) -> Self {
+        let min = self
+            .singlet_fn
+            .min()
+            .min(self.growth_fn.iter().copied().fold(f32::INFINITY, f32::min))
+            .min(
+                self.doublet_fns
+                    .iter()
+                    .map(DoubletFn::min)
+                    .fold(f32::INFINITY, f32::min),
+            )
+            .min(
+                self.triplet_l_fns
+                    .iter()
+                    .map(TripletFn::min)
+                    .fold(f32::INFINITY, f32::min),
+            )
+            .min(
+                self.triplet_i_fns
+                    .iter()
+                    .map(TripletFn::min)
+                    .fold(f32::INFINITY, f32::min),
+            );
+        let max = self
+            .singlet_fn
+            .max()
+            .max(
+                self.growth_fn
+                    .iter()
+                    .copied()
+                    .fold(f32::NEG_INFINITY, f32::max),
+            )
+            .max(
+                self.doublet_fns
+                    .iter()
+                    .map(DoubletFn::max)
+                    .fold(f32::NEG_INFINITY, f32::max),
+            )
+            .max(
+                self.triplet_l_fns
+                    .iter()
+                    .map(TripletFn::max)
+                    .fold(f32::NEG_INFINITY, f32::max),
+            )
+            .max(
+                self.triplet_i_fns
+                    .iter()
+                    .map(TripletFn::max)
+                    .fold(f32::NEG_INFINITY, f32::max),
+            );
+        let scale = 1.0 / (max - min);
+        Self {
+            score_weight: self.score_weight.abs() / scale,
+            singlet_fn: self.singlet_fn.translate(-min).scale(scale),
+            growth_fn: self.growth_fn.map(|value| (value - min) * scale),
+            doublet_fns: self.doublet_fns.map(|f| f.translate(-min).scale(scale)),
+            triplet_l_fns: self.triplet_l_fns.map(|f| f.translate(-min).scale(scale)),
+            triplet_i_fns: self.triplet_i_fns.map(|f| f.translate(-min).scale(scale)),
+            step_size: self.step_size,
+        }
+    }
+
+    fn new(
+        score_weight: f32,
+        singlet_fn: SingletFn,
+        growth_fn: Directions<f32>,
+        doublet_fns: Directions<DoubletFn>,
+        triplet_l_fns: Corners<TripletFn>,
+        triplet_i_fns: Directions<TripletFn>,
+        step_size: Option<f32>,
+    ) -> Self {
+        Self {
+            score_weight,
+            singlet_fn,
+            growth_fn,
+            doublet_fns,
+            triplet_l_fns,
+            triplet_i_fns,
+            step_size,
+        }
+        .rescale()
+    }
+}
+
+impl Genome for OrientedGenome {
+    fn score_weight(&self) -> f32 {
+        self.score_weight
+    }
+
+    fn parameters(&self) -> Vec<f32> {
+        let mut parameters = Vec::with_capacity(Self::NUM_PARAMS);
+        parameters.extend(self.singlet_fn.values());
+        parameters.extend(self.growth_fn.into_array());
+        self.doublet_fns
+            .into_array()
+            .iter()
+            .for_each(|f| parameters.extend(f.values()));
+        self.triplet_l_fns
+            .into_array()
+            .iter()
+            .chain(&self.triplet_i_fns.into_array())
+            .for_each(|f| parameters.extend(f.values()));
+        parameters
+    }
+
+    fn mutate(&self, mutation: &Mutation, rng: &mut Rng) -> GenomeKind {
+        let score_weight = mutation.mutate_weight(self.score_weight, rng);
+        let step_size = mutation.adapt(self.step_size, rng);
+        let mut mutator = mutation.mutator(step_size, Self::NUM_PARAMS, ResetRange::Unit, rng);
+        Self::new(
+            score_weight,
+            self.singlet_fn.mutate(&mut mutator),
+            self.growth_fn.map(&mut mutator),
+            self.doublet_fns.map(|f| f.mutate(&mut mutator)),
+            self.triplet_l_fns.map(|f| f.mutate(&mut mutator)),
+            self.triplet_i_fns.map(|f| f.mutate(&mut mutator)),
+            step_size,
+        )
+        .into()
+    }
+
+    fn score(
+        &self,
+        plant_id: PlantId,
+        tile_id: TileId,
+        context: &PlantContext,
+        grid: &Grid,
+    ) -> Option<f32> {
+        let entity_1 = grid.entity(tile_id).into_greedy(plant_id);
+        match entity_1 {
+            GreedyEntity::Empty => {}
+            GreedyEntity::MyCell(_) => return None,
+            GreedyEntity::OtherCell(_) => {
+                if context.points() <= 1 {
+                    return None;
+                }
+            }
+        }
+
+        let mut score = 0.0;
+        score += self.singlet_fn().score(entity_1);
+        score += grid
+            .doublets(tile_id)
+            .iter()
+            .zip(self.doublet_fns().iter())
+            .enumerate()
+            .fold(0.0, |sum, (idx, (&doublet, doublet_fn))| {
+                let tile_id_2 = doublet.j();
+                let entity_2 = grid.entity(tile_id_2).into_greedy(plant_id);
+                let growth = match entity_2 {
+                    GreedyEntity::MyCell(_) => *self.growth_fn().opposite(idx),
+                    _ => 0.0,
+                };
+                sum + growth + doublet_fn.score(entity_1, entity_2)
+            });
+        score += grid
+            .triplets_l(tile_id)
+            .iter()
+            .zip(self.triplet_l_fns().iter())
+            .fold(0.0, |sum, (&triplet, triplet_fn)| {
+                let tile_id_2 = triplet.j();
+                let tile_id_3 = triplet.k();
+                let entity_2 = grid.entity(tile_id_2).into_greedy(plant_id);
+                let entity_3 = grid.entity(tile_id_3).into_greedy(plant_id);
+                sum + triplet_fn.score(entity_1, entity_2, entity_3)
+            });
+        score += grid
+            .triplets_i(tile_id)
+            .iter()
+            .zip(self.triplet_i_fns().iter())
+            .fold(0.0, |sum, (&triplet, triplet_fn)| {
+                let tile_id_2 = triplet.j();
+                let tile_id_3 = triplet.k();
+                let entity_2 = grid.entity(tile_id_2).into_greedy(plant_id);
+                let entity_3 = grid.entity(tile_id_3).into_greedy(plant_id);
+                sum + triplet_fn.score(entity_1, entity_2, entity_3)
+            });
+        Some(score)
+    }
+}
@@ -2,6 +2,8 @@ use crate::doublet_fn::DoubletFn;
 use crate::entity::GreedyEntity;
 use crate::genome::{Genome, GenomeKind};
 use crate::grid::Grid;
+use crate::mutation::{Mutation, ResetRange};
+use crate::plant_context::PlantContext;
 use crate::plants::PlantId;
 use crate::rand::Rng;
 use crate::singlet_fn::SingletFn;
@@ -17,6 +19,8 @@ struct Config {
     doublet: DoubletFn,
     triplet_l: TripletFn,
     triplet_i: TripletFn,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    step_size: Option<f32>,
 }
 
 #[derive(Debug, Copy, Clone, Getters, Serialize, Deserialize)]
@@ -35,6 +39,8 @@ pub struct TripletGenome {
 
     #[getset(get = "pub")]
     triplet_i_fn: TripletFn,
+
+    step_size: Option<f32>,
 }
 
 impl From<Config> for TripletGenome {
@@ -45,6 +51,7 @@ impl From<Config> for TripletGenome {
             config.doublet,
             config.triplet_l,
             config.triplet_i,
+            config.step_size,
         )
     }
 }
@@ -57,6 +64,7 @@ impl From<TripletGenome> for Config {
             doublet: genome.doublet_fn,
             triplet_l: genome.triplet_l_fn,
             triplet_i: genome.triplet_i_fn,
+            step_size: genome.step_size,
         }
     }
 }
@@ -74,6 +82,7 @@ impl TripletGenome {
             doublet_fn,
             triplet_l_fn,
             triplet_i_fn,
+            None,
         )
     }
 
@@ -97,6 +106,7 @@ impl TripletGenome {
             doublet_fn: self.doublet_fn.translate(-min).scale(scale),
             triplet_l_fn: self.triplet_l_fn.translate(-min).scale(scale),
             triplet_i_fn: self.triplet_i_fn.translate(-min).scale(scale),
+            step_size: self.step_size,
         }
     }
 
@@ -106,6 +116,7 @@ impl TripletGenome {
         doublet_fn: DoubletFn,
         triplet_l_fn: TripletFn,
         triplet_i_fn: TripletFn,
+        step_size: Option<f32>,
     ) -> Self {
         Self {
             score_weight,
@@ -113,6 +124,7 @@ impl TripletGenome {
             doublet_fn,
             triplet_l_fn,
             triplet_i_fn,
+            step_size,
         }
         .rescale()
     }
@@ -123,26 +135,45 @@ impl Genome for TripletGenome {
         self.score_weight
     }
 
-    fn mutate(&self, rng: &mut Rng) -> GenomeKind {
-        let score_weight = self.score_weight + rng.norm() * 0.1 * self.score_weight;
-        let mut mutator = |value| value + rng.norm() * 0.01;
+    fn parameters(&self) -> Vec<f32> {
+        let mut parameters =
+            Vec::with_capacity(SingletFn::LEN + DoubletFn::LEN + 2 * TripletFn::LEN);
+        parameters.extend(self.singlet_fn.values());
+        parameters.extend(self.doublet_fn.values());
+        parameters.extend(self.triplet_l_fn.values());
+        parameters.extend(self.triplet_i_fn.values());
+        parameters
+    }
+
+    fn mutate(&self, mutation: &Mutation, rng: &mut Rng) -> GenomeKind {
+        let score_weight = mutation.mutate_weight(self.score_weight, rng);
+        let step_size = mutation.adapt(self.step_size, rng);
+        let num_params = SingletFn::LEN + DoubletFn::LEN + 2 * TripletFn::LEN;
+        let mut mutator = mutation.mutator(step_size, num_params, ResetRange::Unit, rng);
         Self::new(
             score_weight,
             self.singlet_fn.mutate(&mut mutator),
             self.doublet_fn.mutate(&mut mutator),
             self.triplet_l_fn.mutate(&mut mutator),
             self.triplet_i_fn.mutate(&mut mutator),
+            step_size,
         )
         .into()
     }
 
-    fn score(&self, plant_id: PlantId, tile_id: TileId, points: usize, grid: &Grid) -> Option<f32> {
+    fn score(
+        &self,
+        plant_id: PlantId,
+        tile_id: TileId,
+        context: &PlantContext,
+        grid: &Grid,
+    ) -> Option<f32> {
         let entity_1 = grid.entity(tile_id).into_greedy(plant_id);
         match entity_1 {
             GreedyEntity::Empty => {}
             GreedyEntity::MyCell(_) => return None,
             GreedyEntity::OtherCell(_) => {
-                if points <= 1 {
+                if context.points() <= 1 {
                     return None;
                 }
             }
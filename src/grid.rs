@@ -68,6 +68,10 @@ impl Grid {
         self.grid.id_at(position)
     }
 
+    pub fn position(&self, tile_id: TileId) -> Position {
+        self.grid.position(tile_id)
+    }
+
     pub fn columns(&self) -> impl Iterator<Item = &[Entity]> {
         self.entities.chunks(self.grid.y_size())
     }
@@ -108,6 +112,10 @@ impl Grid {
         old_entity
     }
 
+    pub fn blob(&self, tile_id: TileId) -> &Blob {
+        &self.blobs[tile_id]
+    }
+
     pub fn doublets(&self, tile_id: TileId) -> &[Doublet; 4] {
         &self.doublets[tile_id]
     }
@@ -0,0 +1,91 @@
+use crate::genomes::GenomeId;
+use crate::plants::PlantId;
+use crate::position::Position;
+use crate::render::write_png;
+use crate::snapshot::Snapshot;
+use anyhow::Result;
+use derive_more::Constructor;
+use getset::{CopyGetters, Getters};
+use serde::Serialize;
+use std::path::Path;
+
+const EMPTY_COLOR: u8 = 0;
+const OTHER_PLANT_COLOR: u8 = 1;
+const PLANT_COLOR: u8 = 2;
+const FIRST_HEAT_COLOR: u8 = 3;
+
+#[derive(Debug, Constructor, CopyGetters, Serialize)]
+#[getset(get_copy = "pub")]
+pub struct TileScore {
+    tile_id: usize,
+    position: Position,
+    score: f32,
+    probability: f64,
+}
+
+// Chance of every candidate tile being the next one a plant grows into
+#[derive(Debug, Constructor, CopyGetters, Getters, Serialize)]
+pub struct Heatmap {
+    #[getset(get_copy = "pub")]
+    round: usize,
+    #[getset(get_copy = "pub")]
+    plant_id: PlantId,
+    #[getset(get_copy = "pub")]
+    genome_id: GenomeId,
+    #[getset(get_copy = "pub")]
+    points: usize,
+    #[getset(get_copy = "pub")]
+    score_weight: f32,
+    #[getset(get = "pub")]
+    tiles: Vec<TileScore>,
+}
+
+impl Heatmap {
+    // Draws the board with the plant in dark gray, other plants in light gray and the candidate
+    // tiles from pale yellow to red, scaled to the most likely tile so small differences show
+    pub fn write_png(&self, snapshot: &Snapshot, cell_size: usize, path: &Path) -> Result<()> {
+        let y_size = snapshot.y_size();
+        let width = snapshot.x_size() * cell_size;
+        let height = y_size * cell_size;
+        let own_key = usize::from(self.plant_id) + 1;
+
+        let mut colors = vec![255, 255, 255, 215, 215, 215, 90, 90, 90];
+        let heat_levels = (u8::MAX - FIRST_HEAT_COLOR) as usize + 1;
+        for level in 0..heat_levels {
+            let heat = level as f64 / (heat_levels - 1) as f64;
+            let green = 240.0 * (1.0 - heat);
+            let blue = 160.0 * (1.0 - heat).powi(2);
+            colors.extend([255, green.round() as u8, blue.round() as u8]);
+        }
+
+        let mut tile_colors = snapshot
+            .plants()
+            .iter()
+            .map(|&plant| match plant {
+                0 => EMPTY_COLOR,
+                plant if plant == own_key => PLANT_COLOR,
+                _ => OTHER_PLANT_COLOR,
+            })
+            .collect::<Vec<_>>();
+        let max_probability = self
+            .tiles
+            .iter()
+            .map(|tile| tile.probability)
+            .fold(0.0, f64::max);
+        for tile in &self.tiles {
+            let heat = tile.probability / max_probability.max(f64::MIN_POSITIVE);
+            let level = (heat * (heat_levels - 1) as f64).round() as u8;
+            tile_colors[tile.tile_id] = FIRST_HEAT_COLOR + level;
+        }
+
+        let mut pixels = vec![EMPTY_COLOR; width * height];
+        for (tile, color) in tile_colors.into_iter().enumerate() {
+            let x = tile / y_size * cell_size;
+            let y = tile % y_size * cell_size;
+            for row in y..y + cell_size {
+                pixels[row * width + x..row * width + x + cell_size].fill(color);
+            }
+        }
+        write_png(path, width, height, &colors, &pixels)
+    }
+}
@@ -1,18 +1,45 @@
+use crate::active_genome::ActiveGenome;
+use crate::founder::Founder;
 use crate::genome::GenomeKind;
+use crate::genome_stats::GenomeStats;
 use crate::genomes::GenomeId;
-use derive_more::Constructor;
-use getset::CopyGetters;
-use serde::Serialize;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Constructor, CopyGetters, Serialize)]
+#[derive(Debug, Clone, CopyGetters, Getters, Serialize, Deserialize)]
 pub struct InactiveGenome {
+    #[getset(get_copy = "pub")]
     id: GenomeId,
     #[serde(flatten)]
     #[getset(get = "pub")]
     genome: GenomeKind,
     #[getset(get_copy = "pub")]
     max_yield: usize,
+    #[getset(get_copy = "pub")]
     created_at: usize,
+    #[getset(get_copy = "pub")]
     died_at: usize,
+    #[getset(get_copy = "pub")]
     parent_genome_id: Option<GenomeId>,
+    #[serde(flatten)]
+    #[getset(get = "pub")]
+    founder: Founder,
+    #[serde(flatten)]
+    #[getset(get_copy = "pub")]
+    stats: GenomeStats,
+}
+
+impl InactiveGenome {
+    pub fn new(active_genome: &ActiveGenome, died_at: usize) -> Self {
+        Self {
+            id: active_genome.id(),
+            genome: active_genome.genome().clone(),
+            max_yield: active_genome.max_yield(),
+            created_at: active_genome.created_at(),
+            died_at,
+            parent_genome_id: active_genome.parent_genome_id(),
+            founder: active_genome.founder().clone(),
+            stats: active_genome.stats(),
+        }
+    }
 }
@@ -3,6 +3,7 @@ use crate::plants::PlantId;
 use derive_more::Constructor;
 use getset::CopyGetters;
 
+#[allow(dead_code)]
 #[derive(Debug, Clone, CopyGetters, Default, Constructor)]
 pub struct InactivePlant {
     id: PlantId,
@@ -0,0 +1,84 @@
+use crate::active_genome::ActiveGenome;
+use crate::cell_kind::CellKind;
+use crate::either::Either;
+use crate::genomes::GenomeId;
+use crate::inactive_genome::InactiveGenome;
+use crate::morphology::Morphology;
+use crate::plants::PlantId;
+use crate::position::Position;
+use crate::tiles::TileId;
+use anyhow::{bail, Context, Result};
+use derive_more::Constructor;
+use getset::CopyGetters;
+use serde::Serialize;
+
+#[derive(Debug, Copy, Clone)]
+pub enum Target {
+    Tile(TileId),
+    Plant(PlantId),
+    Genome(GenomeId),
+}
+
+impl Target {
+    pub fn parse(kind: &str, id: &str) -> Result<Self> {
+        let id = id
+            .parse::<usize>()
+            .with_context(|| format!("Invalid {kind} id {id}"))?;
+        match kind {
+            "tile" => Ok(Target::Tile(TileId::from(id))),
+            "plant" => Ok(Target::Plant(PlantId::from(id))),
+            "genome" => Ok(Target::Genome(GenomeId::from(id))),
+            _ => bail!("Unknown inspection target {kind}, expected tile, plant or genome"),
+        }
+    }
+}
+
+#[derive(Debug, Constructor, Serialize)]
+pub struct TileReport {
+    tile_id: usize,
+    position: Position,
+    cell_kind: Option<CellKind>,
+}
+
+#[derive(Debug, Constructor, Serialize)]
+pub struct LivingPlantReport {
+    created_at: usize,
+    age: usize,
+    size: usize,
+    energy_yield: usize,
+    energy_usage: usize,
+    energy_points: usize,
+    morphology: Morphology,
+}
+
+#[derive(Debug, Constructor, CopyGetters, Serialize)]
+pub struct PlantReport {
+    plant_id: PlantId,
+    #[getset(get_copy = "pub")]
+    genome_id: GenomeId,
+    // Only plants still on the board have a shape and energy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    living: Option<LivingPlantReport>,
+}
+
+#[derive(Debug, Constructor, Serialize)]
+pub struct GenomeReport<'a> {
+    age: usize,
+    // Ancestors from the parent back to the founder
+    lineage: Vec<GenomeId>,
+    #[serde(flatten)]
+    genome: &'a Either<ActiveGenome, InactiveGenome>,
+}
+
+// Everything known about whatever sits at a tile, a plant or a genome, filled in as far as the
+// target reaches
+#[derive(Debug, Constructor, Serialize)]
+pub struct Report<'a> {
+    round: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tile: Option<TileReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    plant: Option<PlantReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    genome: Option<GenomeReport<'a>>,
+}
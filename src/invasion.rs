@@ -0,0 +1,194 @@
+use crate::config_file;
+use crate::genome::{Genome, GenomeKind};
+use crate::library::Libraries;
+use crate::mutation::Mutation;
+use crate::placement::Placement;
+use crate::plant_label::PlantLabel;
+use crate::rand::Rng;
+use crate::territory::Territory;
+use crate::world_builder::WorldBuilder;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize)]
+struct Config {
+    x_size: usize,
+    y_size: usize,
+    max_steps: usize,
+    rng_seed: u64,
+    trials: usize,
+    seed_rate: f32,
+    mutation_rate: f32,
+    #[serde(default)]
+    mutation: Mutation,
+    territory: Territory,
+    invaders: usize,
+    #[serde(default)]
+    placement: Placement,
+    genomes: Vec<String>,
+    #[serde(default)]
+    mutants: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Outcome {
+    Takeover,
+    Extinction,
+    // Neither side survived, which is no success for the invaders but no defense either
+    MutualExtinction,
+    Coexistence,
+}
+
+#[derive(Debug, Serialize)]
+struct Trial<'a> {
+    resident: &'a str,
+    invader: &'a str,
+    trial: usize,
+    seed: u64,
+    rounds: usize,
+    outcome: Outcome,
+    resident_area: usize,
+    invader_area: usize,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Summary {
+    resident: String,
+    invader: String,
+    trials: usize,
+    takeovers: usize,
+    extinctions: usize,
+    mutual_extinctions: usize,
+    coexistences: usize,
+    takeover_probability: f64,
+    mean_invader_share: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct InvasionResult {
+    pairs: Vec<Summary>,
+}
+
+// Fills the board with a resident genome and drops a few invaders into it, for every ordered
+// pair of genomes and optionally for mutants of each resident, counting how often the invaders
+// drive the resident extinct
+pub fn run() -> Result<()> {
+    let config: Config = config_file::load("invasion")?;
+    if config.invaders == 0 {
+        bail!("An invasion needs at least one invader");
+    }
+
+    let genomes = Libraries::default().genomes(&config.genomes)?;
+
+    let mut pairs = Vec::new();
+    for resident in 0..genomes.len() {
+        if config.mutants {
+            pairs.push((resident, None));
+        }
+        for invader in (0..genomes.len()).filter(|&invader| invader != resident) {
+            pairs.push((resident, Some(invader)));
+        }
+    }
+    if pairs.is_empty() {
+        bail!("An invasion needs two genomes or mutants enabled");
+    }
+
+    let mut trials = csv::Writer::from_path("invasion_trials.csv")?;
+    let mut summaries = Vec::with_capacity(pairs.len());
+    for (resident, invader) in pairs {
+        let resident_name = &config.genomes[resident];
+        let invader_name = invader.map_or("mutants", |invader| &config.genomes[invader]);
+        let mut summary = Summary {
+            resident: resident_name.clone(),
+            invader: invader_name.to_owned(),
+            ..Default::default()
+        };
+        for trial in 0..config.trials {
+            let seed = config.rng_seed + trial as u64;
+            let invader_genome = invader.map(|invader| &genomes[invader]);
+            let (rounds, resident_area, invader_area) =
+                invade(&config, &genomes[resident], invader_genome, seed)?;
+            let outcome = match (resident_area, invader_area) {
+                (0, 0) => Outcome::MutualExtinction,
+                (_, 0) => Outcome::Extinction,
+                (0, _) => Outcome::Takeover,
+                _ => Outcome::Coexistence,
+            };
+            match outcome {
+                Outcome::Takeover => summary.takeovers += 1,
+                Outcome::Extinction => summary.extinctions += 1,
+                Outcome::MutualExtinction => summary.mutual_extinctions += 1,
+                Outcome::Coexistence => summary.coexistences += 1,
+            }
+            summary.trials += 1;
+            let total_area = (resident_area + invader_area).max(1) as f64;
+            let share = invader_area as f64 / total_area;
+            summary.mean_invader_share +=
+                (share - summary.mean_invader_share) / summary.trials as f64;
+            trials.serialize(Trial {
+                resident: resident_name,
+                invader: invader_name,
+                trial,
+                seed,
+                rounds,
+                outcome,
+                resident_area,
+                invader_area,
+            })?;
+        }
+        trials.flush()?;
+        summary.takeover_probability = summary.takeovers as f64 / summary.trials.max(1) as f64;
+        println!(
+            "{invader_name} invading {resident_name}: {:.3} takeover probability",
+            summary.takeover_probability
+        );
+        summaries.push(summary);
+    }
+
+    let toml = toml::to_string_pretty(&InvasionResult { pairs: summaries })?;
+    std::fs::write("invasion_result.toml", toml)?;
+    Ok(())
+}
+
+// Runs a single invasion until either side is gone or time runs out, returning the number of
+// rounds played and the final areas of the resident and of all invaders together
+fn invade(
+    config: &Config,
+    resident: &GenomeKind,
+    invader: Option<&GenomeKind>,
+    seed: u64,
+) -> Result<(usize, usize, usize)> {
+    let mut rng = Rng::from_seed(seed);
+    let invaders = (0..config.invaders)
+        .map(|_| {
+            let genome = invader
+                .cloned()
+                .unwrap_or_else(|| resident.mutate(&config.mutation, &mut rng));
+            (genome, PlantLabel::default())
+        })
+        .collect();
+
+    // Invaders go first so the territory leaves a clearing around each of them
+    let mut world = WorldBuilder::new(config.x_size, config.y_size);
+    world
+        .seed_rate(config.seed_rate)
+        .mutation_rate(config.mutation_rate)
+        .mutation(config.mutation)
+        .add_placed_plants(invaders, &config.placement, &mut rng)?;
+    let resident_id =
+        world.add_territory(resident.clone(), PlantLabel::default(), &config.territory)?;
+    let mut world = world.build();
+
+    let mut rounds = 0;
+    loop {
+        let areas = world.founder_areas();
+        let resident_area = areas.get(&resident_id).copied().unwrap_or(0);
+        let invader_area = areas.values().sum::<usize>() - resident_area;
+        if rounds == config.max_steps || resident_area == 0 || invader_area == 0 {
+            return Ok((rounds, resident_area, invader_area));
+        }
+        world.step(rounds, &mut rng);
+        rounds += 1;
+    }
+}
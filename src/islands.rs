@@ -0,0 +1,187 @@
+use crate::genomes::GenomeId;
+use crate::rand::Rng;
+use crate::world::{RunOutputs, World};
+use anyhow::{bail, Result};
+use getset::CopyGetters;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Copy, Clone, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topology {
+    #[default]
+    Ring,
+    Full,
+    Random,
+}
+
+#[derive(Debug, Copy, Clone, CopyGetters, Deserialize)]
+pub struct IslandsConfig {
+    #[getset(get_copy = "pub")]
+    count: usize,
+    migration_interval: usize,
+    migrants: usize,
+    #[serde(default)]
+    topology: Topology,
+}
+
+#[derive(Debug, Serialize)]
+struct IslandStats {
+    round: usize,
+    island: usize,
+    plants: usize,
+    genomes: usize,
+    immigrants: usize,
+}
+
+// A genome that settled on another island, traced back to where it came from
+#[derive(Debug, Serialize)]
+struct Migration {
+    round: usize,
+    source: usize,
+    destination: usize,
+    genome_id: GenomeId,
+    founder_id: GenomeId,
+    immigrant_id: GenomeId,
+}
+
+// Worlds that evolve independently, each with its own rng, and exchange their fittest genomes
+// every few rounds
+#[derive(Debug)]
+pub struct Archipelago {
+    islands: Vec<(World, Rng)>,
+    config: IslandsConfig,
+}
+
+impl Archipelago {
+    pub fn new(islands: Vec<(World, Rng)>, config: IslandsConfig) -> Result<Self> {
+        if islands.is_empty() {
+            bail!("The island model needs at least one island");
+        }
+        if config.migration_interval == 0 {
+            bail!("Migration interval must be at least one round");
+        }
+        Ok(Self { islands, config })
+    }
+
+    pub fn run(&mut self, rng: &mut Rng, max_rounds: usize, snapshot_interval: usize) {
+        let mut stats = csv::Writer::from_path("islands.csv").unwrap();
+        let mut migrations = csv::Writer::from_path("migrations.csv").unwrap();
+        // Every island keeps its own records of the run, as the one file would mix their boards
+        let mut outputs = self
+            .islands
+            .iter()
+            .enumerate()
+            .map(|(island, (world, _))| {
+                let dir = Self::dir(island);
+                std::fs::create_dir_all(&dir).unwrap();
+                world.create_outputs(&dir)
+            })
+            .collect::<Vec<RunOutputs>>();
+
+        let mut round = 0;
+        while round < max_rounds {
+            let end = (round + self.config.migration_interval).min(max_rounds);
+            std::thread::scope(|scope| {
+                for ((world, rng), outputs) in self.islands.iter_mut().zip(&mut outputs) {
+                    scope.spawn(move || {
+                        (round..end).for_each(|round| {
+                            let mean_energy = world.step(round, rng);
+                            world.record_round(outputs, round, mean_energy);
+                            if round % snapshot_interval == 0 {
+                                world.record_snapshot(outputs, round);
+                            }
+                        })
+                    });
+                }
+            });
+            round = end;
+
+            let mut immigrants = vec![0; self.islands.len()];
+            for migration in self.migrate(round, rng) {
+                immigrants[migration.destination] += 1;
+                migrations.serialize(migration).unwrap();
+            }
+            migrations.flush().unwrap();
+            for (world, _) in &mut self.islands {
+                world.update_archive(round);
+            }
+            println!("round: {round}");
+            for (island, (world, _)) in self.islands.iter().enumerate() {
+                stats
+                    .serialize(IslandStats {
+                        round,
+                        island,
+                        plants: world.num_plants(),
+                        genomes: world.num_genomes(),
+                        immigrants: immigrants[island],
+                    })
+                    .unwrap();
+            }
+            stats.flush().unwrap();
+        }
+
+        for (island, (world, _)) in self.islands.iter().enumerate() {
+            world.write_results(&Self::dir(island), round);
+        }
+
+        // Every island fills its own copy of the archive, which are merged into one file
+        let mut archives = self
+            .islands
+            .iter_mut()
+            .filter_map(|(world, _)| world.take_archive());
+        if let Some(mut archive) = archives.next() {
+            archives.for_each(|other| archive.merge(other));
+            archive.save().unwrap();
+            println!("Archive holds {} elites", archive.len());
+        }
+    }
+
+    fn dir(island: usize) -> PathBuf {
+        PathBuf::from(format!("island_{island}"))
+    }
+
+    // Sends copies of the fittest genomes of every island to its neighbors, returning where each
+    // immigrant came from
+    fn migrate(&mut self, round: usize, rng: &mut Rng) -> Vec<Migration> {
+        let num_islands = self.islands.len();
+        let mut migrations = Vec::new();
+        if num_islands < 2 {
+            return migrations;
+        }
+
+        let emigrants = self
+            .islands
+            .iter()
+            .map(|(world, _)| world.emigrants(self.config.migrants, round))
+            .collect::<Vec<_>>();
+        for (source, genomes) in emigrants.into_iter().enumerate() {
+            for (genome_id, founder, genome) in genomes {
+                let destinations = match self.config.topology {
+                    Topology::Ring => vec![(source + 1) % num_islands],
+                    Topology::Full => (0..num_islands).filter(|&idx| idx != source).collect(),
+                    Topology::Random => {
+                        vec![(source + 1 + rng.uniform(num_islands - 1)) % num_islands]
+                    }
+                };
+                let label = founder.label().with_tag(format!("island_{source}"));
+                for destination in destinations {
+                    let world = &mut self.islands[destination].0;
+                    if let Some(immigrant_id) =
+                        world.immigrate(genome.clone(), label.clone(), round, rng)
+                    {
+                        migrations.push(Migration {
+                            round,
+                            source,
+                            destination,
+                            genome_id,
+                            founder_id: founder.id(),
+                            immigrant_id,
+                        });
+                    }
+                }
+            }
+        }
+        migrations
+    }
+}
@@ -0,0 +1,79 @@
+use crate::genome::GenomeKind;
+use ahash::AHashMap;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::Path;
+
+// A library is either a table of named genomes or a trial result, whose top genomes are
+// available as `g<id>` and by rank as `top_<n>`
+#[derive(Debug, Clone, Default)]
+pub struct Library {
+    genomes: AHashMap<String, Value>,
+}
+
+impl Library {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read genome library {}", path.display()))?;
+        let value: Value = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&contents)?,
+            _ => toml::from_str(&contents)?,
+        };
+        let Value::Object(mut table) = value else {
+            bail!("Genome library {} is not a table", path.display());
+        };
+
+        let genomes = match table.remove("top_genomes") {
+            Some(Value::Array(top_genomes)) => top_genomes
+                .into_iter()
+                .enumerate()
+                .flat_map(|(idx, genome)| {
+                    let id = genome.get("id").and_then(Value::as_u64);
+                    let by_id = id.map(|id| (format!("g{id}"), genome.clone()));
+                    by_id
+                        .into_iter()
+                        .chain([(format!("top_{}", idx + 1), genome)])
+                })
+                .collect(),
+            _ => table.into_iter().collect(),
+        };
+        Ok(Self { genomes })
+    }
+
+    pub fn genome(&self, name: &str) -> Result<GenomeKind> {
+        let value = self
+            .genomes
+            .get(name)
+            .ok_or_else(|| anyhow!("No genome named {name}"))?;
+        GenomeKind::deserialize(value).with_context(|| format!("Invalid genome {name}"))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Libraries {
+    libraries: AHashMap<String, Library>,
+}
+
+impl Libraries {
+    // Resolves references of the form `library.toml#champion_3`, loading each file once
+    pub fn genome(&mut self, reference: &str) -> Result<GenomeKind> {
+        let (path, name) = reference
+            .split_once('#')
+            .ok_or_else(|| anyhow!("Genome reference {reference} must be <file>#<name>"))?;
+        if !self.libraries.contains_key(path) {
+            let library = Library::load(Path::new(path))?;
+            self.libraries.insert(path.to_owned(), library);
+        }
+        self.libraries[path]
+            .genome(name)
+            .with_context(|| format!("Failed to resolve genome {reference}"))
+    }
+
+    pub fn genomes(&mut self, references: &[String]) -> Result<Vec<GenomeKind>> {
+        references
+            .iter()
+            .map(|reference| self.genome(reference))
+            .collect()
+    }
+}
@@ -0,0 +1,280 @@
+use crate::genomes::GenomeId;
+use crate::inspection::Target;
+use crate::snapshot::Snapshot;
+use crate::viewer::Viewer;
+use crate::world::World;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+const INDEX: &str = include_str!("../live.html");
+const SCRIPT: &str = include_str!("../live.js");
+
+// Frames a client may fall behind by before newer ones are skipped for it
+const CLIENT_BACKLOG: usize = 4;
+const INSPECTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn default_address() -> String {
+    "127.0.0.1:8000".to_owned()
+}
+
+fn default_interval() -> usize {
+    10
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ServeConfig {
+    #[serde(default = "default_address")]
+    address: String,
+    #[serde(default = "default_interval")]
+    interval: usize,
+}
+
+impl Default for ServeConfig {
+    fn default() -> Self {
+        Self {
+            address: default_address(),
+            interval: default_interval(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Frame<'a> {
+    snapshot: &'a Snapshot,
+    plants: usize,
+    genomes: usize,
+    top_genome: Option<GenomeId>,
+    top_yield: usize,
+    finished: bool,
+}
+
+// Inspections are answered by the simulation thread, which owns the world
+type Inspection = (Target, SyncSender<Result<String, String>>);
+
+#[derive(Debug, Default)]
+struct Clients {
+    latest: Option<Arc<str>>,
+    senders: Vec<SyncSender<Arc<str>>>,
+}
+
+// Serves the bundled browser viewer and streams the board to it as server-sent events, with
+// every connection handled on its own thread so the simulation never waits for a browser
+pub struct LiveServer {
+    interval: usize,
+    round: usize,
+    clients: Arc<Mutex<Clients>>,
+    inspections: Receiver<Inspection>,
+}
+
+impl LiveServer {
+    pub fn start(config: &ServeConfig) -> Result<Self> {
+        if config.interval == 0 {
+            bail!("Live viewer interval must be at least one round");
+        }
+        let listener = TcpListener::bind(&config.address)
+            .with_context(|| format!("Failed to listen on {}", config.address))?;
+        println!(
+            "Serving the live viewer on http://{}",
+            listener.local_addr()?
+        );
+
+        let clients = Arc::<Mutex<Clients>>::default();
+        let shared = Arc::clone(&clients);
+        let (inspector, inspections) = mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let clients = Arc::clone(&shared);
+                let inspector = inspector.clone();
+                std::thread::spawn(move || {
+                    // Browsers closing the page show up as write errors, which end the stream
+                    let _ = handle(stream, &clients, &inspector);
+                });
+            }
+        });
+
+        Ok(Self {
+            interval: config.interval,
+            round: 0,
+            clients,
+            inspections,
+        })
+    }
+
+    fn answer(world: &World, round: usize, (target, reply): Inspection) {
+        let report = world
+            .inspect(target, round)
+            .and_then(|report| Ok(serde_json::to_string(&report)?))
+            .map_err(|error| error.to_string());
+        let _ = reply.send(report);
+    }
+
+    fn broadcast(&self, world: &World, round: usize, finished: bool) -> Result<()> {
+        let snapshot = world.board_snapshot(round);
+        let (top_genome, top_yield) = world.top_yield().unzip();
+        let frame = serde_json::to_string(&Frame {
+            snapshot: &snapshot,
+            plants: world.num_plants(),
+            genomes: world.num_genomes(),
+            top_genome,
+            top_yield: top_yield.unwrap_or(0),
+            finished,
+        })?;
+        let frame = Arc::<str>::from(frame);
+
+        let mut clients = self.clients.lock().unwrap();
+        clients.senders.retain(|sender| {
+            !matches!(
+                sender.try_send(Arc::clone(&frame)),
+                Err(TrySendError::Disconnected(_))
+            )
+        });
+        clients.latest = Some(frame);
+        // Closing the streams lets every client pick up the final frame even if its backlog was
+        // full, which it must not miss
+        if finished {
+            clients.senders.clear();
+        }
+        Ok(())
+    }
+}
+
+impl Viewer for LiveServer {
+    #[allow(clippy::manual_is_multiple_of)]
+    fn update(&mut self, world: &World, round: usize) -> Result<bool> {
+        self.round = round;
+        while let Ok(inspection) = self.inspections.try_recv() {
+            Self::answer(world, round, inspection);
+        }
+        if round % self.interval == 0 {
+            self.broadcast(world, round, false)?;
+        }
+        Ok(true)
+    }
+
+    fn finish(&mut self, world: &World, round: usize) -> Result<()> {
+        self.round = round;
+        self.broadcast(world, round, true)
+    }
+
+    fn linger(&mut self, world: &World) -> Result<()> {
+        for inspection in &self.inspections {
+            Self::answer(world, self.round, inspection);
+        }
+        Ok(())
+    }
+}
+
+fn handle(
+    mut stream: TcpStream,
+    clients: &Mutex<Clients>,
+    inspector: &Sender<Inspection>,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // The headers are of no use here but have to be read before answering
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? <= 2 {
+            break;
+        }
+    }
+
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/");
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    match path {
+        "/" | "/index.html" => respond(&mut stream, "200 OK", "text/html; charset=utf-8", INDEX),
+        "/live.js" => respond(
+            &mut stream,
+            "200 OK",
+            "text/javascript; charset=utf-8",
+            SCRIPT,
+        ),
+        "/inspect" => {
+            let (status, content_type, body) = inspect(query, inspector);
+            respond(&mut stream, status, content_type, &body)
+        }
+        "/events" => {
+            let (sender, receiver) = mpsc::sync_channel(CLIENT_BACKLOG);
+            let latest = {
+                let mut clients = clients.lock().unwrap();
+                clients.senders.push(sender);
+                clients.latest.clone()
+            };
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n\r\n"
+            )?;
+            let mut last = None;
+            for frame in latest.into_iter().chain(receiver) {
+                write!(stream, "data: {frame}\n\n")?;
+                stream.flush()?;
+                last = Some(frame);
+            }
+            let latest = clients.lock().unwrap().latest.clone();
+            match latest {
+                Some(frame) if !last.is_some_and(|last| Arc::ptr_eq(&last, &frame)) => {
+                    write!(stream, "data: {frame}\n\n")?;
+                    stream.flush()
+                }
+                _ => Ok(()),
+            }
+        }
+        _ => write!(
+            stream,
+            "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+        ),
+    }
+}
+
+// Asks the simulation thread about a tile, plant or genome, answering with the status, content
+// type and body of the response
+fn inspect(query: &str, inspector: &Sender<Inspection>) -> (&'static str, &'static str, String) {
+    let target = match query.split_once('=') {
+        Some((kind, id)) => Target::parse(kind, id),
+        None => Err(anyhow!("Expected tile, plant or genome")),
+    };
+    let target = match target {
+        Ok(target) => target,
+        Err(error) => return ("400 Bad Request", "text/plain", error.to_string()),
+    };
+
+    let stopped = (
+        "503 Service Unavailable",
+        "text/plain",
+        "The simulation is no longer answering".to_owned(),
+    );
+    let (reply, report) = mpsc::sync_channel(1);
+    if inspector.send((target, reply)).is_err() {
+        return stopped;
+    }
+    match report.recv_timeout(INSPECTION_TIMEOUT) {
+        Ok(Ok(report)) => ("200 OK", "application/json", report),
+        // The world could not find the target
+        Ok(Err(error)) => ("404 Not Found", "text/plain", error),
+        Err(RecvTimeoutError::Timeout) => (
+            "504 Gateway Timeout",
+            "text/plain",
+            "The simulation did not answer in time".to_owned(),
+        ),
+        Err(RecvTimeoutError::Disconnected) => stopped,
+    }
+}
+
+fn respond(
+    stream: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
@@ -1,52 +1,135 @@
 mod active_genome;
 mod active_plant;
+mod archive;
 mod blob;
+mod blob_fn;
 mod cell_kind;
+mod config_file;
+mod directions;
+mod diversity;
 mod doublet;
 mod doublet_fn;
 mod either;
 mod entity;
+mod fitness;
+mod founder;
 mod genome;
+mod genome_stats;
 mod genomes;
 mod grid;
+mod heatmap;
 mod inactive_genome;
 mod inactive_plant;
+mod inspection;
+mod invasion;
+mod islands;
+mod library;
+mod live_server;
+mod mlp;
+mod morphology;
+mod mutation;
 mod organisms;
+mod phylogeny;
+mod placement;
+mod plant_context;
+mod plant_label;
 mod plants;
 mod position;
 mod rand;
+mod render;
 mod simple_graph;
 mod singlet_fn;
+mod snapshot;
 mod square_grid;
 mod step;
+mod terminal_viewer;
+mod territory;
 mod tiles;
+mod time_series;
+mod tournament;
 mod trial_result;
 mod triplet_fn;
 mod triplet_i;
 mod triplet_l;
+mod viewer;
 mod world;
 mod world_builder;
 
+use crate::archive::{Archive, ArchiveConfig};
+use crate::fitness::Fitness;
 use crate::genome::GenomeKind;
+use crate::inspection::Target;
+use crate::islands::{Archipelago, IslandsConfig};
+use crate::library::Libraries;
+use crate::live_server::{LiveServer, ServeConfig};
+use crate::mutation::Mutation;
+use crate::placement::Placement;
+use crate::plant_label::PlantLabel;
+use crate::plants::PlantId;
 use crate::position::Position;
 use crate::rand::Rng;
+use crate::terminal_viewer::{TerminalConfig, TerminalViewer};
+use crate::trial_result::TrialResult;
+use crate::viewer::{Viewer, ViewerKind};
+use crate::world::World;
 use crate::world_builder::WorldBuilder;
-use anyhow::{Context, Result};
-use config::File;
-use serde::Deserialize;
+use anyhow::{bail, Context, Result};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer};
+use std::path::{Path, PathBuf};
 
 #[derive(Deserialize)]
 struct RandomPlantsConfig {
     kind: String,
     total: usize,
+    #[serde(default)]
+    placement: Placement,
+    #[serde(flatten)]
+    label: PlantLabel,
+}
+
+#[derive(Deserialize)]
+struct ResultPlantsConfig {
+    path: PathBuf,
+    top: usize,
+    #[serde(default)]
+    placement: Placement,
+    #[serde(flatten)]
+    label: PlantLabel,
+}
+
+#[allow(clippy::large_enum_variant)]
+enum GenomeConfig {
+    Library { genome: String },
+    Inline(GenomeKind),
+}
+
+impl<'de> Deserialize<'de> for GenomeConfig {
+    // A `genome` key refers to a library and anything else is an inline genome, decided up front
+    // so that a broken inline genome reports its own error rather than matching no variant
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut value = serde_json::Value::deserialize(deserializer)?;
+        match value
+            .as_object_mut()
+            .and_then(|table| table.remove("genome"))
+        {
+            Some(serde_json::Value::String(genome)) => Ok(GenomeConfig::Library { genome }),
+            Some(_) => Err(D::Error::custom("Genome reference must be a string")),
+            None => GenomeKind::deserialize(value)
+                .map(GenomeConfig::Inline)
+                .map_err(D::Error::custom),
+        }
+    }
 }
 
 #[derive(Deserialize)]
 struct StaticPlantsConfig {
     position: Position,
+    #[serde(flatten)]
+    label: PlantLabel,
 
     #[serde(flatten)]
-    genome: GenomeKind,
+    genome: GenomeConfig,
 }
 
 #[derive(Deserialize)]
@@ -57,43 +140,291 @@ struct Config {
     max_steps: usize,
     rng_seed: u64,
     take_top: usize,
+    #[serde(default)]
+    fitness: Fitness,
     seed_rate: f32,
     mutation_rate: f32,
+    #[serde(default)]
+    mutation: Mutation,
     random_plants: Vec<RandomPlantsConfig>,
     static_plants: Vec<StaticPlantsConfig>,
+    #[serde(default)]
+    result_plants: Vec<ResultPlantsConfig>,
+    islands: Option<IslandsConfig>,
+    archive: Option<ArchiveConfig>,
+    terminal: Option<TerminalConfig>,
+    #[serde(default)]
+    serve: ServeConfig,
 }
 
 fn main() -> Result<()> {
-    let config = config::Config::builder()
-        .add_source(File::with_name("config"))
-        .build()?;
-    let config: Config = config
-        .try_deserialize()
-        .context("Failed to deserialize config file")?;
+    match std::env::args().nth(1).as_deref() {
+        None | Some("run") => run(),
+        Some("tournament") => tournament::run(),
+        Some("invasion") => invasion::run(),
+        Some("render") => render::run(),
+        Some("serve") => serve(),
+        Some("inspect") => inspect(),
+        Some("heatmap") => heatmap(),
+        Some(command) => bail!(
+            "Unknown command {command}, expected run, serve, inspect, heatmap, tournament, \
+             invasion or render"
+        ),
+    }
+}
+
+fn load_config() -> Result<Config> {
+    config_file::load("config")
+}
+
+fn run() -> Result<()> {
+    let config = load_config()?;
     let mut rng = Rng::from_seed(config.rng_seed);
 
+    match config.islands {
+        None => {
+            let mut world = build_world(&config, &mut rng, true)?;
+            let mut viewer = config
+                .terminal
+                .map(|terminal| TerminalViewer::new(terminal).map(ViewerKind::from))
+                .transpose()?;
+            world.run(
+                &mut rng,
+                config.max_steps,
+                config.snapshot_interval,
+                viewer.as_mut(),
+            );
+        }
+        Some(_) if config.terminal.is_some() => {
+            bail!("The terminal viewer only follows a single world, not islands")
+        }
+        Some(islands) => {
+            let worlds = (0..islands.count())
+                .map(|island| {
+                    println!("Populating island {island}");
+                    let mut rng = Rng::from_seed(config.rng_seed.wrapping_add(1 + island as u64));
+                    let world = build_world(&config, &mut rng, true)?;
+                    Ok((world, rng))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Archipelago::new(worlds, islands)?.run(
+                &mut rng,
+                config.max_steps,
+                config.snapshot_interval,
+            );
+        }
+    }
+    Ok(())
+}
+
+// Runs a single world while streaming it to the browser viewer, then keeps serving the final
+// round until interrupted
+fn serve() -> Result<()> {
+    let config = load_config()?;
+    if config.islands.is_some() {
+        bail!("The live viewer only follows a single world, not islands");
+    }
+    if config.terminal.is_some() {
+        bail!("The live viewer replaces the terminal viewer, remove the terminal section to serve");
+    }
+    let mut rng = Rng::from_seed(config.rng_seed);
+    let mut world = build_world(&config, &mut rng, true)?;
+    let mut viewer = ViewerKind::from(LiveServer::start(&config.serve)?);
+    world.run(
+        &mut rng,
+        config.max_steps,
+        config.snapshot_interval,
+        Some(&mut viewer),
+    );
+    println!("Run finished, still serving the final round until interrupted");
+    viewer.linger(&world)
+}
+
+// Replays the configured world up to a round and reports on a tile, plant or genome, given as
+// `inspect <round> tile|plant|genome <id>` or `inspect <round> position <x> <y>`
+fn inspect() -> Result<()> {
+    let args = std::env::args().skip(2).collect::<Vec<_>>();
+    let [round, kind, rest @ ..] = args.as_slice() else {
+        bail!("Usage: inspect <round> tile|plant|genome <id> or inspect <round> position <x> <y>");
+    };
+    let config = load_config()?;
+    let (world, round) = replay(&config, round)?;
+    let target = match (kind.as_str(), rest) {
+        ("position", [x, y]) => {
+            let position = Position::new(x.parse()?, y.parse()?);
+            if position.x() >= config.x_size || position.y() >= config.y_size {
+                bail!("Position {x}, {y} is outside the board");
+            }
+            Target::Tile(world.tile_at(position))
+        }
+        (kind, [id]) => Target::parse(kind, id)?,
+        _ => bail!("Expected one id or an x and y position"),
+    };
+
+    let report = world.inspect(target, round)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+// Replays the configured world up to a round and writes where a plant would grow next as
+// heatmap.json and heatmap.png, given as `heatmap <round> <plant> [cell size]`
+fn heatmap() -> Result<()> {
+    let args = std::env::args().skip(2).collect::<Vec<_>>();
+    let (round, plant_id, cell_size) = match args.as_slice() {
+        [round, plant_id] => (round, plant_id, 4),
+        [round, plant_id, cell_size] => (round, plant_id, cell_size.parse()?),
+        _ => bail!("Usage: heatmap <round> <plant> [cell size]"),
+    };
+    if cell_size == 0 {
+        bail!("Cell size must be at least one pixel");
+    }
+    let plant_id = PlantId::from(
+        plant_id
+            .parse::<usize>()
+            .with_context(|| format!("Invalid plant id {plant_id}"))?,
+    );
+
+    let config = load_config()?;
+    let (world, round) = replay(&config, round)?;
+    let heatmap = world.heatmap(plant_id, round)?;
+    std::fs::write("heatmap.json", serde_json::to_string_pretty(&heatmap)?)?;
+    heatmap.write_png(
+        &world.board_snapshot(round),
+        cell_size,
+        Path::new("heatmap.png"),
+    )?;
+
+    println!(
+        "Plant {plant_id} of genome {} with {} points and score weight {} has {} candidate tiles",
+        heatmap.genome_id(),
+        heatmap.points(),
+        heatmap.score_weight(),
+        heatmap.tiles().len()
+    );
+    let mut tiles = heatmap.tiles().iter().collect::<Vec<_>>();
+    tiles.sort_by(|a, b| b.probability().total_cmp(&a.probability()));
+    for tile in tiles.into_iter().take(5) {
+        let position = tile.position();
+        println!(
+            "{:>6.3} at ({}, {}) with score {:.3}",
+            tile.probability(),
+            position.x(),
+            position.y(),
+            tile.score()
+        );
+    }
+    Ok(())
+}
+
+// Steps a fresh copy of the configured world the same way a normal run does, so the replay ends
+// on the board that run reports as the given round, returning it with the round it ended in
+fn replay(config: &Config, round: &str) -> Result<(World, usize)> {
+    let round = round
+        .parse::<usize>()
+        .with_context(|| format!("Invalid round {round}"))?;
+    if config.islands.is_some() {
+        bail!("Replays only cover a single world, not islands");
+    }
+    // The run rewrote the archive file, so reseeding from it would set up a different world
+    if config.archive.is_some() {
+        bail!(
+            "Replays cannot restore the elites a run was seeded with, remove the archive section"
+        );
+    }
+    let mut rng = Rng::from_seed(config.rng_seed);
+    let mut world = build_world(config, &mut rng, false)?;
+
+    let mut last_round = 0;
+    for current in 0..=round {
+        world.step(current, &mut rng);
+        last_round = current;
+        if world.num_plants() < 2 {
+            break;
+        }
+    }
+    if last_round < round {
+        eprintln!("The run ended early in round {last_round}");
+    }
+    Ok((world, last_round))
+}
+
+// Stays silent when `verbose` is off, so that commands printing JSON keep stdout to themselves
+fn build_world(config: &Config, rng: &mut Rng, verbose: bool) -> Result<World> {
     let x_size = config.x_size;
     let y_size = config.y_size;
     let mut world = WorldBuilder::new(x_size, y_size);
     world
         .take_top(config.take_top)
+        .fitness(config.fitness)
         .seed_rate(config.seed_rate)
-        .mutation_rate(config.mutation_rate);
+        .mutation_rate(config.mutation_rate)
+        .mutation(config.mutation);
 
-    for plant_config in config.static_plants {
-        println!("Adding static plant at {:?}", plant_config.position);
-        world.add_plant(plant_config.genome, plant_config.position)?;
+    let mut libraries = Libraries::default();
+    for plant_config in &config.static_plants {
+        let position = plant_config.position;
+        let label = plant_config.label.clone();
+        if verbose {
+            println!("Adding static plant{label} at {position:?}");
+        }
+        let genome = match &plant_config.genome {
+            GenomeConfig::Library { genome } => libraries.genome(genome)?,
+            GenomeConfig::Inline(genome) => genome.clone(),
+        };
+        world.add_plant(genome, label, position)?;
     }
-    for plant_config in config.random_plants {
+    for plant_config in &config.random_plants {
         let total = plant_config.total;
         let kind = &plant_config.kind;
-        println!("Adding {total} {kind} random plants");
-        world.add_random_plants(kind, total, &mut rng)?;
+        let label = plant_config.label.clone();
+        if verbose {
+            println!("Adding {total} {kind} random plants{label}");
+        }
+        world.add_random_plants(kind, total, label, &plant_config.placement, rng)?;
+    }
+    for plant_config in &config.result_plants {
+        let path = &plant_config.path;
+        let top = plant_config.top;
+        let label = plant_config.label.clone();
+        if verbose {
+            println!("Adding top {top} genomes from {}{label}", path.display());
+        }
+        let trial_result = TrialResult::load(path)?;
+        let plants = trial_result
+            .top_genomes()
+            .iter()
+            .take(top)
+            .map(|top_genome| {
+                // Without a group label the genomes keep the label of their own founder
+                let label = if label == PlantLabel::default() {
+                    top_genome.label().clone()
+                } else {
+                    label.clone()
+                };
+                (top_genome.genome().clone(), label)
+            })
+            .collect();
+        world.add_placed_plants(plants, &plant_config.placement, rng)?;
+    }
+    if let Some(archive_config) = &config.archive {
+        let archive = Archive::load(archive_config)?;
+        let plants = archive
+            .reseed(rng)
+            .into_iter()
+            .map(|(cell, genome)| (genome, PlantLabel::new(Some(cell), vec!["elite".into()])))
+            .collect::<Vec<_>>();
+        if !plants.is_empty() {
+            if verbose {
+                println!(
+                    "Adding {} elites from a {} cell archive",
+                    plants.len(),
+                    archive.len()
+                );
+            }
+            world.add_placed_plants(plants, &Placement::Uniform, rng)?;
+        }
+        world.archive(archive);
     }
 
-    let mut world = world.build();
-    let max_steps = config.max_steps;
-    let snapshot_interval = config.snapshot_interval;
-    world.run(&mut rng, max_steps, snapshot_interval);
-    Ok(())
+    Ok(world.build())
 }
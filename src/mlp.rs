@@ -0,0 +1,144 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Config {
+    hidden_weights: Vec<Vec<f32>>,
+    hidden_biases: Vec<f32>,
+    output_weights: Vec<f32>,
+    output_bias: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "Config", into = "Config")]
+pub struct Mlp {
+    hidden_weights: Vec<Vec<f32>>,
+    hidden_biases: Vec<f32>,
+    output_weights: Vec<f32>,
+    output_bias: f32,
+}
+
+impl TryFrom<Config> for Mlp {
+    type Error = anyhow::Error;
+
+    fn try_from(config: Config) -> Result<Self> {
+        let hidden_size = config.hidden_biases.len();
+        if hidden_size == 0 {
+            bail!("Neural network needs at least one hidden unit");
+        }
+        if config.hidden_weights.len() != hidden_size || config.output_weights.len() != hidden_size
+        {
+            bail!("Neural network layers do not match {hidden_size} hidden units");
+        }
+        let input_size = config.hidden_weights[0].len();
+        if config
+            .hidden_weights
+            .iter()
+            .any(|weights| weights.len() != input_size)
+        {
+            bail!("Neural network hidden weights must all have {input_size} inputs");
+        }
+
+        Ok(Self {
+            hidden_weights: config.hidden_weights,
+            hidden_biases: config.hidden_biases,
+            output_weights: config.output_weights,
+            output_bias: config.output_bias,
+        })
+    }
+}
+
+impl From<Mlp> for Config {
+    fn from(mlp: Mlp) -> Self {
+        Config {
+            hidden_weights: mlp.hidden_weights,
+            hidden_biases: mlp.hidden_biases,
+            output_weights: mlp.output_weights,
+            output_bias: mlp.output_bias,
+        }
+    }
+}
+
+impl Mlp {
+    pub fn from_fn(input_size: usize, hidden_size: usize, mut f: impl FnMut(usize) -> f32) -> Self {
+        let hidden_weights = (0..hidden_size)
+            .map(|_| (0..input_size).map(|_| f(input_size)).collect())
+            .collect();
+        let hidden_biases = (0..hidden_size).map(|_| f(input_size)).collect();
+        let output_weights = (0..hidden_size).map(|_| f(hidden_size)).collect();
+        let output_bias = f(hidden_size);
+        Self {
+            hidden_weights,
+            hidden_biases,
+            output_weights,
+            output_bias,
+        }
+    }
+
+    pub fn input_size(&self) -> usize {
+        self.hidden_weights[0].len()
+    }
+
+    pub fn num_params(&self) -> usize {
+        let hidden_size = self.hidden_biases.len();
+        hidden_size * (self.input_size() + 2) + 1
+    }
+
+    // Appends inputs with zero weights, which leaves the output unchanged
+    pub fn pad_inputs(mut self, input_size: usize) -> Self {
+        self.hidden_weights
+            .iter_mut()
+            .for_each(|weights| weights.resize(input_size.max(weights.len()), 0.0));
+        self
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = f32> + '_ {
+        self.hidden_weights
+            .iter()
+            .flatten()
+            .chain(&self.hidden_biases)
+            .chain(&self.output_weights)
+            .chain(std::iter::once(&self.output_bias))
+            .copied()
+    }
+
+    pub fn mutate(&self, mut mutator: impl FnMut(f32) -> f32) -> Self {
+        let hidden_weights = self
+            .hidden_weights
+            .iter()
+            .map(|weights| weights.iter().map(|&weight| mutator(weight)).collect())
+            .collect();
+        let hidden_biases = self
+            .hidden_biases
+            .iter()
+            .map(|&bias| mutator(bias))
+            .collect();
+        let output_weights = self
+            .output_weights
+            .iter()
+            .map(|&weight| mutator(weight))
+            .collect();
+        let output_bias = mutator(self.output_bias);
+        Self {
+            hidden_weights,
+            hidden_biases,
+            output_weights,
+            output_bias,
+        }
+    }
+
+    pub fn evaluate(&self, inputs: &[f32]) -> f32 {
+        self.hidden_weights
+            .iter()
+            .zip(&self.hidden_biases)
+            .zip(&self.output_weights)
+            .fold(self.output_bias, |sum, ((weights, bias), output_weight)| {
+                let activation = weights
+                    .iter()
+                    .zip(inputs)
+                    .fold(*bias, |sum, (weight, input)| sum + weight * input)
+                    .tanh();
+                sum + output_weight * activation
+            })
+    }
+}
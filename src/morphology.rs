@@ -0,0 +1,225 @@
+use crate::grid::Grid;
+use crate::position::Position;
+use getset::CopyGetters;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Copy, Clone, Default, CopyGetters, Serialize)]
+#[getset(get_copy = "pub")]
+pub struct Morphology {
+    area: usize,
+    perimeter: usize,
+    width: usize,
+    height: usize,
+    // Number of cells with zero to four neighboring cells of the same plant
+    degrees: [usize; 5],
+    holes: usize,
+}
+
+impl Morphology {
+    pub fn new(positions: &[Position], perimeter: usize, degrees: [usize; 5], grid: &Grid) -> Self {
+        let (x_start, width) = Self::extent(positions.iter().map(|p| p.x()), grid.x_size());
+        let (y_start, height) = Self::extent(positions.iter().map(|p| p.y()), grid.y_size());
+        let holes = Self::count_holes(positions, (x_start, y_start), (width, height), grid);
+        Self {
+            area: positions.len(),
+            perimeter,
+            width,
+            height,
+            degrees,
+            holes,
+        }
+    }
+
+    // Perimeter of a square with the same area over the actual perimeter, so 1 for a square and
+    // lower for elongated or ragged shapes
+    pub fn compactness(&self) -> f64 {
+        if self.perimeter == 0 {
+            return 1.0;
+        }
+        let compactness = 4.0 * (self.area as f64).sqrt() / self.perimeter as f64;
+        compactness.min(1.0)
+    }
+
+    pub fn perimeter_ratio(&self) -> f64 {
+        self.perimeter as f64 / self.area.max(1) as f64
+    }
+
+    // Share of the bounding box covered by cells
+    pub fn fill(&self) -> f64 {
+        self.area as f64 / (self.width * self.height).max(1) as f64
+    }
+
+    // Share of cells that are the tip of a branch
+    pub fn branchiness(&self) -> f64 {
+        self.degrees[1] as f64 / self.area.max(1) as f64
+    }
+
+    // Shortest wrapped interval covering all coordinates, found by skipping the largest gap of
+    // unused coordinates around the torus
+    fn extent(coordinates: impl Iterator<Item = usize>, size: usize) -> (usize, usize) {
+        let mut used = vec![false; size];
+        coordinates.for_each(|coordinate| used[coordinate] = true);
+        let Some(first) = used.iter().position(|&used| used) else {
+            return (0, 0);
+        };
+
+        let (mut gap_start, mut gap_length) = (0, 0);
+        let mut length = 0;
+        for offset in 1..=size {
+            let coordinate = (first + offset) % size;
+            if used[coordinate] {
+                if length > gap_length {
+                    gap_start = (coordinate + size - length) % size;
+                    gap_length = length;
+                }
+                length = 0;
+            } else {
+                length += 1;
+            }
+        }
+        ((gap_start + gap_length) % size, size - gap_length)
+    }
+
+    // Empty regions inside the bounding box that cannot reach its border
+    fn count_holes(
+        positions: &[Position],
+        (x_start, y_start): (usize, usize),
+        (width, height): (usize, usize),
+        grid: &Grid,
+    ) -> usize {
+        // Pad the box by one tile on every side so the outside is a single connected region
+        let (padded_width, padded_height) = (width + 2, height + 2);
+        let mut filled = vec![false; padded_width * padded_height];
+        positions.iter().for_each(|position| {
+            let x = (position.x() + grid.x_size() - x_start) % grid.x_size() + 1;
+            let y = (position.y() + grid.y_size() - y_start) % grid.y_size() + 1;
+            filled[x * padded_height + y] = true;
+        });
+
+        let mut regions: usize = 0;
+        for start in 0..filled.len() {
+            if filled[start] {
+                continue;
+            }
+            regions += 1;
+            let mut stack = vec![start];
+            filled[start] = true;
+            while let Some(idx) = stack.pop() {
+                let (x, y) = (idx / padded_height, idx % padded_height);
+                let neighbors = [
+                    (x > 0).then(|| idx - padded_height),
+                    (x + 1 < padded_width).then(|| idx + padded_height),
+                    (y > 0).then(|| idx - 1),
+                    (y + 1 < padded_height).then(|| idx + 1),
+                ];
+                for neighbor in neighbors.into_iter().flatten() {
+                    if !filled[neighbor] {
+                        filled[neighbor] = true;
+                        stack.push(neighbor);
+                    }
+                }
+            }
+        }
+        // The first region found is always the padded outside
+        regions.saturating_sub(1)
+    }
+}
+
+// Average form of the living plants of a genome
+#[derive(Debug, Copy, Clone, Default, CopyGetters, Serialize, Deserialize)]
+#[getset(get_copy = "pub")]
+pub struct MorphologySummary {
+    plants: usize,
+    mean_area: f64,
+    mean_perimeter: f64,
+    mean_width: f64,
+    mean_height: f64,
+    mean_compactness: f64,
+    mean_perimeter_ratio: f64,
+    mean_fill: f64,
+    mean_branchiness: f64,
+    mean_holes: f64,
+    degrees: [usize; 5],
+}
+
+impl MorphologySummary {
+    pub fn add(&mut self, morphology: &Morphology) {
+        self.plants += 1;
+        let n = self.plants as f64;
+        let mean = |mean: &mut f64, value: f64| *mean += (value - *mean) / n;
+        mean(&mut self.mean_area, morphology.area as f64);
+        mean(&mut self.mean_perimeter, morphology.perimeter as f64);
+        mean(&mut self.mean_width, morphology.width as f64);
+        mean(&mut self.mean_height, morphology.height as f64);
+        mean(&mut self.mean_compactness, morphology.compactness());
+        mean(&mut self.mean_perimeter_ratio, morphology.perimeter_ratio());
+        mean(&mut self.mean_fill, morphology.fill());
+        mean(&mut self.mean_branchiness, morphology.branchiness());
+        mean(&mut self.mean_holes, morphology.holes as f64);
+        self.degrees
+            .iter_mut()
+            .zip(morphology.degrees)
+            .for_each(|(total, count)| *total += count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::square_grid::SquareGrid;
+
+    fn morphology(positions: &[(usize, usize)]) -> Morphology {
+        let grid = Grid::new(SquareGrid::new(10, 10));
+        let positions = positions
+            .iter()
+            .map(|&(x, y)| Position::new(x, y))
+            .collect::<Vec<_>>();
+        Morphology::new(&positions, 0, [0; 5], &grid)
+    }
+
+    // Every tile of a square of side `side` with its corner at `start`, except for `skip`
+    fn square(start: (usize, usize), side: usize, skip: &[(usize, usize)]) -> Vec<(usize, usize)> {
+        (0..side)
+            .flat_map(|dx| (0..side).map(move |dy| ((start.0 + dx) % 10, (start.1 + dy) % 10)))
+            .filter(|position| !skip.contains(position))
+            .collect()
+    }
+
+    #[test]
+    fn single_cell() {
+        let morphology = morphology(&[(3, 4)]);
+        assert_eq!(morphology.area(), 1);
+        assert_eq!((morphology.width(), morphology.height()), (1, 1));
+        assert_eq!(morphology.holes(), 0);
+    }
+
+    #[test]
+    fn ring_with_hole() {
+        let morphology = morphology(&square((2, 2), 3, &[(3, 3)]));
+        assert_eq!(morphology.area(), 8);
+        assert_eq!((morphology.width(), morphology.height()), (3, 3));
+        assert_eq!(morphology.holes(), 1);
+    }
+
+    #[test]
+    fn ring_wrapping_around_the_edges() {
+        let morphology = morphology(&square((9, 9), 3, &[(0, 0)]));
+        assert_eq!((morphology.width(), morphology.height()), (3, 3));
+        assert_eq!(morphology.holes(), 1);
+    }
+
+    #[test]
+    fn block_wrapping_around_the_corner() {
+        let morphology = morphology(&square((9, 9), 2, &[]));
+        assert_eq!((morphology.width(), morphology.height()), (2, 2));
+        assert_eq!(morphology.holes(), 0);
+    }
+
+    #[test]
+    fn extent_skips_the_largest_gap() {
+        assert_eq!(Morphology::extent([8, 9, 0, 1].into_iter(), 10), (8, 4));
+        assert_eq!(Morphology::extent([2, 5].into_iter(), 10), (2, 4));
+        assert_eq!(Morphology::extent(0..10, 10).1, 10);
+        assert_eq!(Morphology::extent(std::iter::empty(), 10), (0, 0));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::rand::Rng;
+use serde::Deserialize;
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MutationKind {
+    Gaussian { sigma: f32 },
+    Cauchy { scale: f32 },
+    Sparse { sigma: f32 },
+    Reset { rate: f32 },
+}
+
+impl MutationKind {
+    fn strength(&self) -> f32 {
+        match *self {
+            MutationKind::Gaussian { sigma } => sigma,
+            MutationKind::Cauchy { scale } => scale,
+            MutationKind::Sparse { sigma } => sigma,
+            MutationKind::Reset { rate } => rate,
+        }
+    }
+}
+
+// Where a reset draws a parameter from, which depends on how the genome scales its parameters
+#[derive(Debug, Copy, Clone)]
+pub enum ResetRange {
+    // Table genomes are rescaled into [0, 1]
+    Unit,
+    // Network weights are signed and centered on zero
+    Normal,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+pub struct SelfAdaptive {
+    tau: f32,
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+pub struct Mutation {
+    #[serde(flatten)]
+    kind: MutationKind,
+    #[serde(default = "Mutation::default_weight_sigma")]
+    weight_sigma: f32,
+    #[serde(default)]
+    self_adaptive: Option<SelfAdaptive>,
+}
+
+impl Default for Mutation {
+    fn default() -> Self {
+        Self {
+            kind: MutationKind::Gaussian { sigma: 0.01 },
+            weight_sigma: Self::default_weight_sigma(),
+            self_adaptive: None,
+        }
+    }
+}
+
+impl Mutation {
+    fn default_weight_sigma() -> f32 {
+        0.1
+    }
+
+    pub fn mutate_weight(&self, score_weight: f32, rng: &mut Rng) -> f32 {
+        score_weight + rng.norm() * self.weight_sigma * score_weight
+    }
+
+    // Log-normal update of the step size carried by the genome, as in evolution strategies
+    pub fn adapt(&self, step_size: Option<f32>, rng: &mut Rng) -> Option<f32> {
+        self.self_adaptive.map(|SelfAdaptive { tau }| {
+            let step_size = step_size.unwrap_or_else(|| self.kind.strength());
+            let step_size = step_size * (tau * rng.norm()).exp();
+            match self.kind {
+                // The reset rate is a probability, which would otherwise drift past one
+                MutationKind::Reset { .. } => step_size.clamp(0.0, 1.0),
+                _ => step_size,
+            }
+        })
+    }
+
+    pub fn mutator<'a>(
+        &self,
+        step_size: Option<f32>,
+        num_params: usize,
+        reset_range: ResetRange,
+        rng: &'a mut Rng,
+    ) -> impl FnMut(f32) -> f32 + 'a {
+        let kind = self.kind;
+        let strength = step_size.unwrap_or_else(|| kind.strength());
+        let target = match kind {
+            MutationKind::Sparse { .. } => rng.uniform(num_params),
+            _ => 0,
+        };
+        let mut index = 0;
+        move |value| {
+            let new_value = match kind {
+                MutationKind::Gaussian { .. } => value + rng.norm() * strength,
+                MutationKind::Cauchy { .. } => value + rng.cauchy() * strength,
+                MutationKind::Sparse { .. } if index == target => value + rng.norm() * strength,
+                MutationKind::Sparse { .. } => value,
+                MutationKind::Reset { .. } if rng.sample() < strength => match reset_range {
+                    ResetRange::Unit => rng.sample(),
+                    ResetRange::Normal => rng.norm(),
+                },
+                MutationKind::Reset { .. } => value,
+            };
+            index += 1;
+            new_value
+        }
+    }
+}
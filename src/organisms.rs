@@ -1,14 +1,22 @@
 use crate::active_genome::ActiveGenome;
 use crate::active_plant::ActivePlant;
 use crate::either::Either::{self, *};
-use crate::genome::GenomeKind;
+use crate::fitness::Fitness;
+use crate::founder::Founder;
+use crate::genome::{Genome, GenomeKind};
+use crate::genome_stats::GenomeStats;
 use crate::genomes::{GenomeId, Genomes};
 use crate::grid::Grid;
 use crate::inactive_genome::InactiveGenome;
 use crate::inactive_plant::InactivePlant;
+use crate::mutation::Mutation;
+use crate::plant_context::PlantContext;
+use crate::plant_label::PlantLabel;
 use crate::plants::{PlantId, Plants};
 use crate::rand::Rng;
 use crate::tiles::TileId;
+use crate::time_series::EventCounts;
+use ahash::AHashMap;
 use itertools::Itertools;
 
 #[derive(Debug, Clone, Default)]
@@ -17,6 +25,9 @@ pub struct Organisms {
     active_genomes: Vec<GenomeId>,
     plants: Plants,
     active_plants: Vec<PlantId>,
+    events: EventCounts,
+    lineage_sizes: AHashMap<GenomeId, usize>,
+    extinctions: Vec<Founder>,
 }
 
 impl Organisms {
@@ -24,6 +35,26 @@ impl Organisms {
         &self.active_plants
     }
 
+    pub fn active_genomes(&self) -> &[GenomeId] {
+        &self.active_genomes
+    }
+
+    pub fn take_events(&mut self) -> EventCounts {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn take_extinctions(&mut self) -> Vec<Founder> {
+        std::mem::take(&mut self.extinctions)
+    }
+
+    pub fn genomes(&self) -> &Genomes {
+        &self.genomes
+    }
+
+    pub fn plants(&self) -> &Plants {
+        &self.plants
+    }
+
     pub fn plant(&self, plant_id: PlantId) -> &ActivePlant {
         self.plants[plant_id].as_ref().unwrap_living()
     }
@@ -40,20 +71,41 @@ impl Organisms {
             .as_mut()
             .unwrap_living();
         genome.set_max_yield(energy_yield);
+        genome.stats_mut().add_cell();
+    }
+
+    pub fn takeover(&mut self, plant_id: PlantId, old_plant_id: PlantId) {
+        let genome_id = self.plant(plant_id).genome_id();
+        self.genome_stats_mut(genome_id).add_takeover();
+        let old_genome_id = self.plant(old_plant_id).genome_id();
+        self.genome_stats_mut(old_genome_id).add_loss();
+    }
+
+    pub fn harvest(&mut self, plant_id: PlantId, grid: &Grid) -> usize {
+        let active_plant = self.plant(plant_id);
+        let energy_yield = active_plant.energy_yield(grid);
+        let energy_points = active_plant.energy_points(energy_yield, grid);
+        let genome_id = active_plant.genome_id();
+        self.genome_stats_mut(genome_id).add_yield(energy_yield);
+        energy_points
     }
 
     pub fn abandon(&mut self, plant_id: PlantId, tile_id: TileId, grid: &Grid) -> Vec<TileId> {
-        self.plants[plant_id]
-            .as_mut()
-            .unwrap_living()
-            .abandon(tile_id, grid)
+        let active_plant = self.plants[plant_id].as_mut().unwrap_living();
+        let dead_cells = active_plant.abandon(tile_id, grid);
+        let genome_id = active_plant.genome_id();
+        self.genome_stats_mut(genome_id)
+            .remove_cells(1 + dead_cells.len());
+        dead_cells
     }
 
-    pub fn add_plant(&mut self, genome_id: GenomeId) -> PlantId {
+    pub fn add_plant(&mut self, genome_id: GenomeId, round: usize) -> PlantId {
         self.increment_genome(genome_id);
 
+        self.events.births += 1;
+
         let id = PlantId::from(self.plants.len());
-        let active_plant = ActivePlant::new(id, genome_id);
+        let active_plant = ActivePlant::new(id, genome_id, round);
         self.plants.push(Living(active_plant));
         self.active_plants.push(id);
         id
@@ -63,30 +115,46 @@ impl Organisms {
         &mut self,
         genome_id: GenomeId,
         round: usize,
+        mutation: &Mutation,
         rng: &mut Rng,
     ) -> PlantId {
-        let new_genome = self.genome(genome_id).mutate(rng);
-        let new_genome_id = self.add_genome(new_genome, Some(genome_id), round);
-        self.add_plant(new_genome_id)
+        self.events.mutations += 1;
+        let new_genome = self.genome(genome_id).mutate(mutation, rng);
+        let founder = self.genome(genome_id).founder().clone();
+        let new_genome_id = self.add_genome(new_genome, Some(genome_id), founder, round);
+        self.add_plant(new_genome_id, round)
     }
 
     pub fn remove_plant(&mut self, plant_id: PlantId, round: usize) {
         let active_plant = self.plant(plant_id);
         let genome_id = active_plant.genome_id();
+        let size = active_plant.size();
+        let lifespan = round - active_plant.created_at();
+        let stats = self.genome_stats_mut(genome_id);
+        stats.remove_cells(size);
+        stats.remove_plant(lifespan);
         self.decrement_genome(genome_id, round);
+        self.events.deaths += 1;
 
         self.active_plants.retain(|&id| id != plant_id);
         self.plants[plant_id] = Dead(InactivePlant::new(plant_id, genome_id));
     }
 
-    pub fn add_genome(
+    pub fn add_founder(&mut self, genome: GenomeKind, label: PlantLabel, round: usize) -> GenomeId {
+        let id = GenomeId::from(self.genomes.len());
+        self.add_genome(genome, None, Founder::new(id, label), round)
+    }
+
+    fn add_genome(
         &mut self,
         genome: GenomeKind,
         parent_genome_id: Option<GenomeId>,
+        founder: Founder,
         round: usize,
     ) -> GenomeId {
         let id = GenomeId::from(self.genomes.len());
-        let active_genome = ActiveGenome::new(id, genome, parent_genome_id, round);
+        *self.lineage_sizes.entry(founder.id()).or_default() += 1;
+        let active_genome = ActiveGenome::new(id, genome, parent_genome_id, round, founder);
         self.genomes.push(Living(active_genome));
         self.active_genomes.push(id);
         id
@@ -97,25 +165,87 @@ impl Organisms {
         plant_id: PlantId,
         grid: &Grid,
         points: usize,
+        round: usize,
         rng: &mut Rng,
     ) -> Option<TileId> {
         let active_plant = self.plant(plant_id);
         let available_tiles = active_plant.available_tiles();
-        let genome_id = active_plant.genome_id();
-        let active_genome = self.genome(genome_id);
-        active_genome.choose_tile(grid, &available_tiles, plant_id, points, rng)
+        let active_genome = self.genome(active_plant.genome_id());
+        let context = self.plant_context(plant_id, grid, points, round);
+        active_genome.choose_tile(grid, &available_tiles, plant_id, &context, rng)
+    }
+
+    // Score and selection weight of every tile the plant could grow into next
+    pub fn tile_weights(
+        &self,
+        plant_id: PlantId,
+        grid: &Grid,
+        points: usize,
+        round: usize,
+    ) -> Vec<(TileId, f32, f32)> {
+        let active_plant = self.plant(plant_id);
+        let available_tiles = active_plant.available_tiles();
+        let active_genome = self.genome(active_plant.genome_id());
+        let context = self.plant_context(plant_id, grid, points, round);
+        active_genome.tile_weights(grid, &available_tiles, plant_id, &context)
     }
 
-    pub fn top_genomes(&mut self, n: usize) -> Vec<&Either<ActiveGenome, InactiveGenome>> {
-        (&self.genomes)
+    fn plant_context(
+        &self,
+        plant_id: PlantId,
+        grid: &Grid,
+        points: usize,
+        round: usize,
+    ) -> PlantContext {
+        let active_plant = self.plant(plant_id);
+        if self
+            .genome(active_plant.genome_id())
+            .genome()
+            .senses_plant()
+        {
+            active_plant.context(points, round, grid)
+        } else {
+            PlantContext::from_points(points)
+        }
+    }
+
+    pub fn top_genomes(
+        &self,
+        n: usize,
+        fitness: Fitness,
+        round: usize,
+    ) -> Vec<(f64, &Either<ActiveGenome, InactiveGenome>)> {
+        let values = fitness.evaluate(&self.genomes, round, n);
+        values
             .into_iter()
-            .k_largest_by_key(n, |genome| match genome {
-                Living(active_genome) => active_genome.max_yield(),
-                Dead(inactive_genome) => inactive_genome.max_yield(),
+            .zip(&self.genomes)
+            .k_largest_by(n, |(a, _), (b, _)| fitness.compare(*a, *b))
+            .collect()
+    }
+
+    pub fn top_living_genomes(
+        &self,
+        n: usize,
+        fitness: Fitness,
+        round: usize,
+    ) -> Vec<&ActiveGenome> {
+        let values = fitness.evaluate_living(&self.genomes, round, n);
+        values
+            .into_iter()
+            .zip(&self.genomes)
+            .filter_map(|(value, genome)| match genome {
+                Living(active_genome) => Some((value, active_genome)),
+                Dead(_) => None,
             })
+            .k_largest_by(n, |(a, _), (b, _)| fitness.compare(*a, *b))
+            .map(|(_, active_genome)| active_genome)
             .collect()
     }
 
+    fn genome_stats_mut(&mut self, genome_id: GenomeId) -> &mut GenomeStats {
+        self.genomes[genome_id].as_mut().unwrap_living().stats_mut()
+    }
+
     fn increment_genome(&mut self, genome_id: GenomeId) {
         self.genomes[genome_id].as_mut().unwrap_living().increment();
     }
@@ -130,19 +260,15 @@ impl Organisms {
     }
 
     fn remove_genome(&mut self, genome_id: GenomeId, round: usize) {
-        let active_genome = self.genome(genome_id);
-        let genome = active_genome.genome();
-        let max_yield = active_genome.max_yield();
-        let created_at = active_genome.created_at();
-        let parent_genome_id = active_genome.parent_genome_id();
-        self.genomes[genome_id] = Dead(InactiveGenome::new(
-            genome_id,
-            genome.clone(),
-            max_yield,
-            created_at,
-            round,
-            parent_genome_id,
-        ));
+        let inactive_genome = InactiveGenome::new(self.genome(genome_id), round);
+        let founder = inactive_genome.founder();
+        let lineage_size = self.lineage_sizes.get_mut(&founder.id()).unwrap();
+        *lineage_size -= 1;
+        if *lineage_size == 0 {
+            self.lineage_sizes.remove(&founder.id());
+            self.extinctions.push(founder.clone());
+        }
+        self.genomes[genome_id] = Dead(inactive_genome);
         self.active_genomes.retain(|&id| id != genome_id);
     }
 }
@@ -0,0 +1,130 @@
+use crate::either::Either::*;
+use crate::founder::Founder;
+use crate::genomes::{GenomeId, Genomes};
+use serde::Serialize;
+use std::fmt::Write;
+
+#[derive(Debug, Serialize)]
+struct Node {
+    id: GenomeId,
+    parent_genome_id: Option<GenomeId>,
+    #[serde(flatten)]
+    founder: Founder,
+    created_at: usize,
+    died_at: Option<usize>,
+    max_yield: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct Edge {
+    source: GenomeId,
+    target: GenomeId,
+    length: usize,
+}
+
+enum Step {
+    Enter(GenomeId),
+    Exit(GenomeId),
+    Separator,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Phylogeny {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+impl Phylogeny {
+    pub fn new(genomes: &Genomes) -> Self {
+        let nodes: Vec<Node> = genomes
+            .into_iter()
+            .map(|genome| match genome {
+                Living(active_genome) => Node {
+                    id: active_genome.id(),
+                    parent_genome_id: active_genome.parent_genome_id(),
+                    founder: active_genome.founder().clone(),
+                    created_at: active_genome.created_at(),
+                    died_at: None,
+                    max_yield: active_genome.max_yield(),
+                },
+                Dead(inactive_genome) => Node {
+                    id: inactive_genome.id(),
+                    parent_genome_id: inactive_genome.parent_genome_id(),
+                    founder: inactive_genome.founder().clone(),
+                    created_at: inactive_genome.created_at(),
+                    died_at: Some(inactive_genome.died_at()),
+                    max_yield: inactive_genome.max_yield(),
+                },
+            })
+            .collect();
+
+        let edges = nodes
+            .iter()
+            .filter_map(|node| {
+                let parent_id = node.parent_genome_id?;
+                let parent = &nodes[usize::from(parent_id)];
+                Some(Edge {
+                    source: parent_id,
+                    target: node.id,
+                    length: node.created_at - parent.created_at,
+                })
+            })
+            .collect();
+
+        Self { nodes, edges }
+    }
+
+    // Founders hang off an unnamed root with branch lengths measured from round 0
+    pub fn to_newick(&self) -> String {
+        let mut children = vec![Vec::new(); self.nodes.len()];
+        let mut roots = Vec::new();
+        self.nodes
+            .iter()
+            .for_each(|node| match node.parent_genome_id {
+                Some(parent_id) => children[usize::from(parent_id)].push(node.id),
+                None => roots.push(node.id),
+            });
+
+        let mut stack = Vec::new();
+        Self::push_siblings(&mut stack, &roots);
+
+        let mut newick = String::from("(");
+        while let Some(step) = stack.pop() {
+            match step {
+                Step::Enter(id) if children[usize::from(id)].is_empty() => {
+                    self.write_label(&mut newick, id);
+                }
+                Step::Enter(id) => {
+                    newick.push('(');
+                    stack.push(Step::Exit(id));
+                    Self::push_siblings(&mut stack, &children[usize::from(id)]);
+                }
+                Step::Exit(id) => {
+                    newick.push(')');
+                    self.write_label(&mut newick, id);
+                }
+                Step::Separator => newick.push(','),
+            }
+        }
+        newick.push_str(");");
+        newick
+    }
+
+    fn push_siblings(stack: &mut Vec<Step>, siblings: &[GenomeId]) {
+        siblings.iter().rev().enumerate().for_each(|(idx, &id)| {
+            if idx > 0 {
+                stack.push(Step::Separator);
+            }
+            stack.push(Step::Enter(id));
+        });
+    }
+
+    fn write_label(&self, newick: &mut String, id: GenomeId) {
+        let node = &self.nodes[usize::from(id)];
+        let parent_created_at = node
+            .parent_genome_id
+            .map_or(0, |parent_id| self.nodes[usize::from(parent_id)].created_at);
+        let length = node.created_at - parent_created_at;
+        write!(newick, "g{id}:{length}").unwrap();
+    }
+}
@@ -0,0 +1,205 @@
+use crate::position::Position;
+use crate::rand::Rng;
+use crate::square_grid::SquareGrid;
+use crate::tiles::TileId;
+use anyhow::{bail, Result};
+use nohash::IntSet;
+use serde::Deserialize;
+use std::f32::consts::TAU;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Placement {
+    #[default]
+    Uniform,
+    Lattice,
+    Ring {
+        center: Option<Position>,
+        radius: Option<f32>,
+    },
+    Clustered {
+        clusters: usize,
+        spread: f32,
+    },
+    Regions {
+        rows: usize,
+        columns: usize,
+        quotas: Option<Vec<usize>>,
+    },
+    PoissonDisk {
+        min_distance: f32,
+    },
+}
+
+impl Placement {
+    // Picks `total` distinct tiles out of the unused ones; patterns with fixed target positions
+    // fall back to the nearest unused tile when the target is already taken
+    pub fn tiles(
+        &self,
+        total: usize,
+        grid: &SquareGrid,
+        unused_tiles: &IntSet<TileId>,
+        rng: &mut Rng,
+    ) -> Result<Vec<TileId>> {
+        if total > unused_tiles.len() {
+            bail!("Not enough empty tiles to place {total} plants");
+        }
+
+        let mut unused_tiles = unused_tiles.clone();
+        match self {
+            Placement::Uniform => {
+                let mut empty_tiles = unused_tiles.iter().copied().collect::<Vec<_>>();
+                rng.shuffle(&mut empty_tiles);
+                empty_tiles.truncate(total);
+                Ok(empty_tiles)
+            }
+            Placement::Lattice => {
+                let x_size = grid.x_size() as f32;
+                let y_size = grid.y_size() as f32;
+                let columns = (total as f32 * x_size / y_size).sqrt().ceil().max(1.0) as usize;
+                let rows = total.div_ceil(columns);
+                let targets = (0..total).map(|idx| {
+                    let x = ((idx % columns) as f32 + 0.5) * x_size / columns as f32;
+                    let y = ((idx / columns) as f32 + 0.5) * y_size / rows as f32;
+                    (x, y)
+                });
+                Ok(Self::nearest_tiles(targets, grid, &mut unused_tiles))
+            }
+            Placement::Ring { center, radius } => {
+                let x_size = grid.x_size() as f32;
+                let y_size = grid.y_size() as f32;
+                let (center_x, center_y) = center
+                    .map(|center| (center.x() as f32, center.y() as f32))
+                    .unwrap_or((x_size / 2.0, y_size / 2.0));
+                let radius = radius.unwrap_or(x_size.min(y_size) / 4.0);
+                let targets = (0..total).map(|idx| {
+                    let angle = TAU * idx as f32 / total as f32;
+                    (
+                        center_x + radius * angle.cos(),
+                        center_y + radius * angle.sin(),
+                    )
+                });
+                Ok(Self::nearest_tiles(targets, grid, &mut unused_tiles))
+            }
+            Placement::Clustered { clusters, spread } => {
+                if *clusters == 0 {
+                    bail!("Clustered placement needs at least one cluster");
+                }
+                let centers = (0..*clusters)
+                    .map(|_| {
+                        let x = rng.uniform(grid.x_size()) as f32;
+                        let y = rng.uniform(grid.y_size()) as f32;
+                        (x, y)
+                    })
+                    .collect::<Vec<_>>();
+                let targets = (0..total)
+                    .map(|idx| {
+                        let (x, y) = centers[idx % clusters];
+                        (x + spread * rng.norm(), y + spread * rng.norm())
+                    })
+                    .collect::<Vec<_>>();
+                Ok(Self::nearest_tiles(targets, grid, &mut unused_tiles))
+            }
+            Placement::Regions {
+                rows,
+                columns,
+                quotas,
+            } => {
+                let num_regions = rows * columns;
+                if num_regions == 0 {
+                    bail!("Region placement needs at least one row and one column");
+                }
+                let quotas = match quotas {
+                    Some(quotas) if quotas.len() != num_regions => {
+                        bail!(
+                            "Expected {num_regions} region quotas but got {}",
+                            quotas.len()
+                        )
+                    }
+                    Some(quotas) if quotas.iter().sum::<usize>() != total => {
+                        bail!("Region quotas must add up to {total} plants")
+                    }
+                    Some(quotas) => quotas.clone(),
+                    None => (0..num_regions)
+                        .map(|idx| total / num_regions + usize::from(idx < total % num_regions))
+                        .collect(),
+                };
+
+                let mut regions = vec![Vec::new(); num_regions];
+                unused_tiles.iter().for_each(|&tile_id| {
+                    let position = grid.position(tile_id);
+                    let column = position.x() * columns / grid.x_size();
+                    let row = position.y() * rows / grid.y_size();
+                    regions[row * columns + column].push(tile_id);
+                });
+
+                let mut tile_ids = Vec::with_capacity(total);
+                for (idx, (mut region, quota)) in regions.into_iter().zip(quotas).enumerate() {
+                    if quota > region.len() {
+                        bail!("Not enough empty tiles to place {quota} plants in region {idx}");
+                    }
+                    rng.shuffle(&mut region);
+                    tile_ids.extend_from_slice(&region[..quota]);
+                }
+                Ok(tile_ids)
+            }
+            Placement::PoissonDisk { min_distance } => {
+                // Dart throwing over the shuffled empty tiles
+                let mut candidates = unused_tiles.iter().copied().collect::<Vec<_>>();
+                rng.shuffle(&mut candidates);
+                let mut positions: Vec<Position> = Vec::with_capacity(total);
+                let tile_ids = candidates
+                    .into_iter()
+                    .filter(|&tile_id| {
+                        let position = grid.position(tile_id);
+                        let accepted = positions
+                            .iter()
+                            .all(|&other| grid.distance(position, other) >= *min_distance);
+                        if accepted {
+                            positions.push(position);
+                        }
+                        accepted
+                    })
+                    .take(total)
+                    .collect::<Vec<_>>();
+                if tile_ids.len() < total {
+                    bail!(
+                        "Could only place {} plants {min_distance} tiles apart",
+                        tile_ids.len()
+                    );
+                }
+                Ok(tile_ids)
+            }
+        }
+    }
+
+    fn nearest_tiles(
+        targets: impl IntoIterator<Item = (f32, f32)>,
+        grid: &SquareGrid,
+        unused_tiles: &mut IntSet<TileId>,
+    ) -> Vec<TileId> {
+        targets
+            .into_iter()
+            .map(|(x, y)| {
+                let x = (x.round() as isize).rem_euclid(grid.x_size() as isize) as usize;
+                let y = (y.round() as isize).rem_euclid(grid.y_size() as isize) as usize;
+                let target = Position::new(x, y);
+                let tile_id = grid.id_at(target);
+                let tile_id = if unused_tiles.contains(&tile_id) {
+                    tile_id
+                } else {
+                    *unused_tiles
+                        .iter()
+                        .min_by(|&&a, &&b| {
+                            let distance_a = grid.distance(target, grid.position(a));
+                            let distance_b = grid.distance(target, grid.position(b));
+                            distance_a.total_cmp(&distance_b)
+                        })
+                        .unwrap()
+                };
+                unused_tiles.remove(&tile_id);
+                tile_id
+            })
+            .collect()
+    }
+}
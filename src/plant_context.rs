@@ -0,0 +1,25 @@
+use derive_more::Constructor;
+use getset::CopyGetters;
+
+#[derive(Debug, Copy, Clone, Default, Constructor, CopyGetters, Hash, PartialEq, Eq)]
+#[getset(get_copy = "pub")]
+pub struct PlantContext {
+    points: usize,
+    size: usize,
+    age: usize,
+    energy_yield: usize,
+    energy_usage: usize,
+}
+
+impl PlantContext {
+    pub fn from_points(points: usize) -> Self {
+        Self {
+            points,
+            ..Default::default()
+        }
+    }
+
+    pub fn energy_balance(&self) -> isize {
+        self.energy_yield as isize - self.energy_usage as isize
+    }
+}
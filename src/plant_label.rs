@@ -0,0 +1,35 @@
+use derive_more::Constructor;
+use getset::Getters;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Constructor, Getters, Serialize, Deserialize)]
+pub struct PlantLabel {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[getset(get = "pub")]
+    name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[getset(get = "pub")]
+    tags: Vec<String>,
+}
+
+impl PlantLabel {
+    pub fn with_tag(&self, tag: String) -> Self {
+        let mut label = self.clone();
+        if !label.tags.contains(&tag) {
+            label.tags.push(tag);
+        }
+        label
+    }
+}
+
+impl std::fmt::Display for PlantLabel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = &self.name {
+            write!(f, " \"{name}\"")?;
+        }
+        if !self.tags.is_empty() {
+            write!(f, " [{}]", self.tags.join(", "))?;
+        }
+        Ok(())
+    }
+}
@@ -2,9 +2,22 @@ use crate::active_plant::ActivePlant;
 use crate::either::Either;
 use crate::inactive_plant::InactivePlant;
 use derive_more::{Display, From, Into};
+use serde::Serialize;
 
 #[derive(
-    Debug, Copy, Clone, Default, Display, Hash, PartialEq, Eq, PartialOrd, Ord, From, Into,
+    Debug,
+    Copy,
+    Clone,
+    Default,
+    Display,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    From,
+    Into,
+    Serialize,
 )]
 pub struct PlantId(usize);
 
@@ -1,8 +1,20 @@
 use derive_more::Constructor;
 use getset::CopyGetters;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, Constructor, CopyGetters, Deserialize, Hash, PartialEq, Eq)]
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    Default,
+    Constructor,
+    CopyGetters,
+    Serialize,
+    Deserialize,
+    Hash,
+    PartialEq,
+    Eq,
+)]
 pub struct Position {
     #[get_copy = "pub"]
     x: usize,
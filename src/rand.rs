@@ -2,7 +2,7 @@ use rand::distributions::{Distribution, Uniform};
 use rand::rngs::SmallRng;
 use rand::seq::SliceRandom;
 use rand::SeedableRng;
-use rand_distr::StandardNormal;
+use rand_distr::{Cauchy, StandardNormal};
 
 #[derive(Debug, Clone)]
 pub struct Rng(SmallRng);
@@ -16,6 +16,10 @@ impl Rng {
         StandardNormal.sample(&mut self.0)
     }
 
+    pub fn cauchy(&mut self) -> f32 {
+        Cauchy::new(0.0, 1.0).unwrap().sample(&mut self.0)
+    }
+
     pub fn sample(&mut self) -> f32 {
         Uniform::new(0.0, 1.0).sample(&mut self.0)
     }
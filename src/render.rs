@@ -0,0 +1,213 @@
+use crate::config_file;
+use crate::snapshot::Snapshot;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+const NUM_COLORS: usize = 256;
+
+#[derive(Debug, Copy, Clone, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Palette {
+    #[default]
+    Plant,
+    Genome,
+    Founder,
+    CellKind,
+}
+
+fn default_input() -> PathBuf {
+    PathBuf::from("snapshots.jsonl")
+}
+
+fn default_output() -> PathBuf {
+    PathBuf::from("frames")
+}
+
+fn default_cell_size() -> usize {
+    4
+}
+
+fn default_frame_delay() -> u16 {
+    20
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct Config {
+    #[serde(default = "default_input")]
+    input: PathBuf,
+    #[serde(default = "default_output")]
+    output: PathBuf,
+    #[serde(default = "default_cell_size")]
+    cell_size: usize,
+    #[serde(default)]
+    palette: Palette,
+    #[serde(default)]
+    first_frame: usize,
+    last_frame: Option<usize>,
+    // Hundredths of a second each frame stays on screen in the animation
+    #[serde(default = "default_frame_delay")]
+    frame_delay: u16,
+    #[serde(default = "default_true")]
+    png: bool,
+    #[serde(default = "default_true")]
+    gif: bool,
+}
+
+// Turns the snapshots of a run into PNG frames and an animated GIF, reading render.toml if it
+// exists and falling back to defaults otherwise
+pub fn run() -> Result<()> {
+    let config: Config = config_file::load_optional("render")?;
+    if config.cell_size == 0 {
+        bail!("Cell size must be at least one pixel");
+    }
+    if config
+        .last_frame
+        .is_some_and(|last| last < config.first_frame)
+    {
+        bail!("Last frame comes before first frame");
+    }
+    std::fs::create_dir_all(&config.output)?;
+
+    let colors = colors();
+    let mut animation = None;
+    let mut frames = 0;
+    for (idx, snapshot) in Snapshot::read_all(&config.input)?.enumerate() {
+        if idx < config.first_frame {
+            continue;
+        }
+        if config.last_frame.is_some_and(|last| idx > last) {
+            break;
+        }
+        let snapshot = snapshot?;
+        let width = snapshot.x_size() * config.cell_size;
+        let height = snapshot.y_size() * config.cell_size;
+        let pixels = pixels(&snapshot, config.palette, config.cell_size);
+
+        if config.png {
+            let path = config.output.join(format!("frame_{idx:05}.png"));
+            write_png(&path, width, height, &colors, &pixels)?;
+        }
+        if config.gif {
+            if animation.is_none() {
+                let path = config.output.join("animation.gif");
+                animation = Some(gif_encoder(&path, width, height, &colors)?);
+            }
+            let frame = gif::Frame {
+                width: width as u16,
+                height: height as u16,
+                buffer: Cow::Borrowed(&pixels),
+                delay: config.frame_delay,
+                ..Default::default()
+            };
+            animation.as_mut().unwrap().write_frame(&frame)?;
+        }
+        frames += 1;
+    }
+
+    println!("Rendered {frames} frames into {}", config.output.display());
+    Ok(())
+}
+
+// Palette index of every pixel, row by row
+fn pixels(snapshot: &Snapshot, palette: Palette, cell_size: usize) -> Vec<u8> {
+    let width = snapshot.x_size() * cell_size;
+    let height = snapshot.y_size() * cell_size;
+    let mut pixels = vec![0; width * height];
+    for (tile, key) in tile_keys(snapshot, palette).into_iter().enumerate() {
+        let color = color_index(key);
+        let x = tile / snapshot.y_size() * cell_size;
+        let y = tile % snapshot.y_size() * cell_size;
+        for row in y..y + cell_size {
+            pixels[row * width + x..row * width + x + cell_size].fill(color);
+        }
+    }
+    pixels
+}
+
+// What every tile is colored by, zero for empty tiles
+pub fn tile_keys(snapshot: &Snapshot, palette: Palette) -> Vec<usize> {
+    snapshot
+        .plants()
+        .iter()
+        .enumerate()
+        .map(|(tile, &plant)| match palette {
+            _ if plant == 0 => 0,
+            Palette::Plant => plant,
+            Palette::Genome => snapshot.genomes()[&plant] + 1,
+            Palette::Founder => snapshot.founders()[&snapshot.genomes()[&plant]] + 1,
+            Palette::CellKind => snapshot.kinds()[tile].map_or(1, |kind| kind as usize + 1),
+        })
+        .collect()
+}
+
+pub fn color_index(key: usize) -> u8 {
+    if key == 0 {
+        0
+    } else {
+        (1 + (key - 1) % (NUM_COLORS - 1)) as u8
+    }
+}
+
+// White for empty tiles followed by hues spread by the golden ratio so that neighboring keys
+// get clearly different colors
+pub fn colors() -> Vec<u8> {
+    let mut colors = vec![255, 255, 255];
+    for idx in 1..NUM_COLORS {
+        let hue = (idx as f64 * 0.618_033_988_75).fract() * 6.0;
+        let (saturation, value) = (0.65, 0.9);
+        let chroma = value * saturation;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let (r, g, b) = match hue as usize {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let offset = value - chroma;
+        colors.extend([r, g, b].map(|channel| ((channel + offset) * 255.0).round() as u8));
+    }
+    colors
+}
+
+pub fn write_png(
+    path: &Path,
+    width: usize,
+    height: usize,
+    colors: &[u8],
+    pixels: &[u8],
+) -> Result<()> {
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create frame {}", path.display()))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+    encoder.set_color(png::ColorType::Indexed);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_palette(colors);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(pixels)?;
+    Ok(())
+}
+
+fn gif_encoder(
+    path: &Path,
+    width: usize,
+    height: usize,
+    colors: &[u8],
+) -> Result<gif::Encoder<BufWriter<std::fs::File>>> {
+    if width > u16::MAX as usize || height > u16::MAX as usize {
+        bail!("Frames of {width}x{height} pixels are too large for a GIF");
+    }
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create animation {}", path.display()))?;
+    let mut encoder = gif::Encoder::new(BufWriter::new(file), width as u16, height as u16, colors)?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+    Ok(encoder)
+}
@@ -181,6 +181,20 @@ impl SimpleGraph {
         occupied_neighbor_ids
     }
 
+    // Number of distinct tiles bordering the graph
+    pub fn perimeter(&self) -> usize {
+        self.surface.unoccupied_map.len()
+    }
+
+    // How many nodes have zero to four occupied neighbors
+    pub fn degrees(&self) -> [usize; 5] {
+        let mut degrees = [0; 5];
+        self.node_map.keys().for_each(|&node_id| {
+            degrees[self.neighbor_iter(node_id).count()] += 1;
+        });
+        degrees
+    }
+
     pub fn all_unoccupied_neighbors(&self) -> Vec<TileId> {
         self.surface.unoccupied_neighbors()
     }
@@ -10,6 +10,8 @@ pub struct SingletFn {
 }
 
 impl SingletFn {
+    pub const LEN: usize = 2;
+
     pub fn from_fn(mut f: impl FnMut() -> f32) -> Self {
         Self {
             singlet_e: f(),
@@ -38,6 +40,10 @@ impl SingletFn {
         }
     }
 
+    pub fn values(&self) -> [f32; Self::LEN] {
+        [self.singlet_e, self.singlet_o]
+    }
+
     pub fn min(&self) -> f32 {
         self.singlet_e.min(self.singlet_o)
     }
@@ -0,0 +1,43 @@
+use crate::cell_kind::CellKind;
+use anyhow::{Context, Result};
+use derive_more::Constructor;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+// Board state of a single round, written one per line to snapshots.jsonl so runs can be
+// rendered without the browser viewer
+#[derive(Debug, Clone, Constructor, CopyGetters, Getters, Serialize, Deserialize)]
+pub struct Snapshot {
+    #[getset(get_copy = "pub")]
+    round: usize,
+    #[getset(get_copy = "pub")]
+    x_size: usize,
+    #[getset(get_copy = "pub")]
+    y_size: usize,
+    // Plant id plus one of every tile in column order, zero for empty tiles
+    #[getset(get = "pub")]
+    plants: Vec<usize>,
+    #[getset(get = "pub")]
+    kinds: Vec<Option<CellKind>>,
+    // Genome id of every plant on the board, keyed like the tiles above
+    #[getset(get = "pub")]
+    genomes: BTreeMap<usize, usize>,
+    // Founder id of every genome on the board
+    #[getset(get = "pub")]
+    founders: BTreeMap<usize, usize>,
+}
+
+impl Snapshot {
+    pub fn read_all(path: &Path) -> Result<impl Iterator<Item = Result<Snapshot>>> {
+        let file = std::fs::File::open(path)
+            .with_context(|| format!("Failed to open snapshots {}", path.display()))?;
+        let lines = BufReader::new(file).lines().enumerate().map(|(idx, line)| {
+            let line = line?;
+            serde_json::from_str(&line).with_context(|| format!("Failed to parse snapshot {idx}"))
+        });
+        Ok(lines)
+    }
+}
@@ -95,6 +95,20 @@ impl SquareGrid {
         Self::id(position.x(), position.y(), self.y_size)
     }
 
+    pub fn position(&self, tile_id: TileId) -> Position {
+        let idx = usize::from(tile_id);
+        Position::new(idx / self.y_size, idx % self.y_size)
+    }
+
+    // Euclidean distance on the torus, taking the shorter way around each axis
+    pub fn distance(&self, a: Position, b: Position) -> f32 {
+        let dx = a.x().abs_diff(b.x());
+        let dy = a.y().abs_diff(b.y());
+        let dx = dx.min(self.x_size - dx) as f32;
+        let dy = dy.min(self.y_size - dy) as f32;
+        (dx * dx + dy * dy).sqrt()
+    }
+
     pub fn id_from(&self, tile_id: TileId, walk: &[Step]) -> TileId {
         let mut tile_id = tile_id;
         for step in walk {
@@ -0,0 +1,209 @@
+use crate::render::{color_index, colors, tile_keys, Palette};
+use crate::viewer::Viewer;
+use crate::world::World;
+use ahash::AHashMap;
+use anyhow::{bail, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::{cursor, execute, queue, terminal};
+use serde::Deserialize;
+use std::io::{Stdout, Write};
+use std::time::{Duration, Instant};
+
+const MAX_DELAY: Duration = Duration::from_secs(5);
+const STATUS_LINES: u16 = 2;
+
+fn default_interval() -> usize {
+    10
+}
+
+fn default_delay_ms() -> u64 {
+    100
+}
+
+fn default_palette() -> Palette {
+    Palette::Founder
+}
+
+#[derive(Debug, Copy, Clone, Deserialize)]
+pub struct TerminalConfig {
+    #[serde(default = "default_interval")]
+    interval: usize,
+    #[serde(default = "default_delay_ms")]
+    delay_ms: u64,
+    #[serde(default = "default_palette")]
+    palette: Palette,
+}
+
+// Draws a downsampled board with live stats on an alternate screen, two tiles per character
+// using half blocks. Keys: p or space pauses, s steps one round while paused, + and - change the
+// speed and q stops the run early.
+pub struct TerminalViewer {
+    stdout: Stdout,
+    interval: usize,
+    delay: Duration,
+    palette: Palette,
+    colors: Vec<u8>,
+    paused: bool,
+    active: bool,
+}
+
+impl TerminalViewer {
+    pub fn new(config: TerminalConfig) -> Result<Self> {
+        if config.interval == 0 {
+            bail!("Terminal viewer interval must be at least one round");
+        }
+        let mut stdout = std::io::stdout();
+        terminal::enable_raw_mode()?;
+        execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self {
+            stdout,
+            interval: config.interval,
+            delay: Duration::from_millis(config.delay_ms),
+            palette: config.palette,
+            colors: colors(),
+            paused: false,
+            active: true,
+        })
+    }
+
+    // Hands the terminal back so the end of the run can be printed as usual
+    fn restore(&mut self) {
+        if self.active {
+            let _ = execute!(
+                self.stdout,
+                ResetColor,
+                cursor::Show,
+                terminal::LeaveAlternateScreen
+            );
+            let _ = terminal::disable_raw_mode();
+            self.active = false;
+        }
+    }
+
+    fn draw(&mut self, world: &World, round: usize) -> std::io::Result<()> {
+        let (columns, rows) = terminal::size()?;
+        let snapshot = world.board_snapshot(round);
+        let keys = tile_keys(&snapshot, self.palette);
+        let (x_size, y_size) = (snapshot.x_size(), snapshot.y_size());
+
+        // Every character covers a block of tiles and shows the most common key in each half
+        let width = (columns as usize).clamp(1, x_size);
+        let height = (2 * rows.saturating_sub(STATUS_LINES) as usize).clamp(1, y_size);
+        let block_key = |column: usize, row: usize| {
+            if row >= height {
+                return 0;
+            }
+            let mut counts = AHashMap::<usize, usize>::default();
+            for x in column * x_size / width..(column + 1) * x_size / width {
+                for y in row * y_size / height..(row + 1) * y_size / height {
+                    *counts.entry(keys[x * y_size + y]).or_default() += 1;
+                }
+            }
+            counts
+                .into_iter()
+                .max_by_key(|&(key, count)| (count, std::cmp::Reverse(key)))
+                .map_or(0, |(key, _)| key)
+        };
+
+        let lines = height.div_ceil(2);
+        for line in 0..lines {
+            queue!(self.stdout, cursor::MoveTo(0, line as u16))?;
+            for column in 0..width {
+                let top = self.color(block_key(column, 2 * line));
+                let bottom = self.color(block_key(column, 2 * line + 1));
+                queue!(
+                    self.stdout,
+                    SetForegroundColor(top),
+                    SetBackgroundColor(bottom),
+                    Print('▀')
+                )?;
+            }
+            queue!(self.stdout, ResetColor)?;
+        }
+
+        let state = if self.paused { "paused" } else { "running" };
+        let top_yield = match world.top_yield() {
+            Some((genome_id, max_yield)) => format!("{max_yield} (genome {genome_id})"),
+            None => "none".to_owned(),
+        };
+        queue!(
+            self.stdout,
+            cursor::MoveTo(0, lines as u16),
+            terminal::Clear(terminal::ClearType::FromCursorDown),
+            Print(format!(
+                "round {round} | plants {} | genomes {} | top yield {top_yield}",
+                world.num_plants(),
+                world.num_genomes(),
+            )),
+            cursor::MoveTo(0, lines as u16 + 1),
+            Print(format!(
+                "{state} | {} ms per frame | p pause  s step  + faster  - slower  q quit",
+                self.delay.as_millis()
+            )),
+        )?;
+        self.stdout.flush()
+    }
+
+    fn color(&self, key: usize) -> Color {
+        let idx = 3 * color_index(key) as usize;
+        let [r, g, b] = [self.colors[idx], self.colors[idx + 1], self.colors[idx + 2]];
+        Color::Rgb { r, g, b }
+    }
+}
+
+impl Viewer for TerminalViewer {
+    // Redraws the board every `interval` rounds, or every round while paused so that a step is a
+    // single round, and handles keys until it is time for the next round, returning false once
+    // the user asks to quit
+    #[allow(clippy::manual_is_multiple_of)]
+    fn update(&mut self, world: &World, round: usize) -> Result<bool> {
+        if !self.paused && round % self.interval != 0 {
+            return Ok(true);
+        }
+        self.draw(world, round)?;
+
+        let deadline = Instant::now() + self.delay;
+        loop {
+            let has_event = if self.paused {
+                true
+            } else {
+                let timeout = deadline.saturating_duration_since(Instant::now());
+                !timeout.is_zero() && event::poll(timeout)?
+            };
+            if !has_event {
+                return Ok(true);
+            }
+
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+                    KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(false)
+                    }
+                    KeyCode::Char('p') | KeyCode::Char(' ') => self.paused = !self.paused,
+                    KeyCode::Char('s') if self.paused => return Ok(true),
+                    KeyCode::Char('+') => self.delay /= 2,
+                    KeyCode::Char('-') => {
+                        self.delay = (self.delay * 2).clamp(Duration::from_millis(1), MAX_DELAY)
+                    }
+                    _ => {}
+                },
+                Event::Resize(_, _) => {}
+                _ => continue,
+            }
+            self.draw(world, round)?;
+        }
+    }
+
+    fn finish(&mut self, _world: &World, _round: usize) -> Result<()> {
+        self.restore();
+        Ok(())
+    }
+}
+
+impl Drop for TerminalViewer {
+    fn drop(&mut self) {
+        self.restore();
+    }
+}
@@ -0,0 +1,46 @@
+use crate::position::Position;
+use crate::square_grid::SquareGrid;
+use crate::tiles::TileId;
+use anyhow::{bail, Result};
+use serde::Deserialize;
+
+// A rectangle pre-filled with square patches of cells, each patch a separate plant, separated by
+// empty gaps so the plants have room to harvest energy
+#[derive(Debug, Copy, Clone, Deserialize)]
+pub struct Territory {
+    #[serde(default)]
+    origin: Position,
+    width: Option<usize>,
+    height: Option<usize>,
+    patch_size: usize,
+    gap: usize,
+}
+
+impl Territory {
+    pub fn patches(&self, grid: &SquareGrid) -> Result<Vec<Vec<TileId>>> {
+        if self.patch_size == 0 {
+            bail!("Territory patches need at least one cell");
+        }
+        let width = self.width.unwrap_or(grid.x_size()).min(grid.x_size());
+        let height = self.height.unwrap_or(grid.y_size()).min(grid.y_size());
+        let stride = self.patch_size + self.gap;
+
+        let patches = (0..width)
+            .step_by(stride)
+            .flat_map(|x| (0..height).step_by(stride).map(move |y| (x, y)))
+            .map(|(x, y)| {
+                let x_end = (x + self.patch_size).min(width);
+                let y_end = (y + self.patch_size).min(height);
+                (x..x_end)
+                    .flat_map(|dx| (y..y_end).map(move |dy| (dx, dy)))
+                    .map(|(dx, dy)| {
+                        let x = (self.origin.x() + dx) % grid.x_size();
+                        let y = (self.origin.y() + dy) % grid.y_size();
+                        grid.id_at(Position::new(x, y))
+                    })
+                    .collect()
+            })
+            .collect();
+        Ok(patches)
+    }
+}
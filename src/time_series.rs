@@ -0,0 +1,22 @@
+use serde::Serialize;
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct EventCounts {
+    pub births: usize,
+    pub deaths: usize,
+    pub mutations: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RoundStats {
+    pub round: usize,
+    pub plants: usize,
+    pub genomes: usize,
+    pub occupied_fraction: f64,
+    pub mean_size: f64,
+    pub max_size: usize,
+    pub mean_energy: f64,
+    pub births: usize,
+    pub deaths: usize,
+    pub mutations: usize,
+}
@@ -0,0 +1,191 @@
+use crate::config_file;
+use crate::genome::GenomeKind;
+use crate::genomes::GenomeId;
+use crate::library::Libraries;
+use crate::mutation::Mutation;
+use crate::plant_label::PlantLabel;
+use crate::position::Position;
+use crate::rand::Rng;
+use crate::world_builder::WorldBuilder;
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+const INITIAL_RATING: f64 = 1500.0;
+
+fn default_k_factor() -> f64 {
+    16.0
+}
+
+#[derive(Deserialize)]
+struct Config {
+    x_size: usize,
+    y_size: usize,
+    max_steps: usize,
+    rng_seed: u64,
+    games: usize,
+    seed_rate: f32,
+    mutation_rate: f32,
+    #[serde(default)]
+    mutation: Mutation,
+    #[serde(default = "default_k_factor")]
+    k_factor: f64,
+    positions: Option<[Position; 2]>,
+    entrants: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct Game<'a> {
+    a: &'a str,
+    b: &'a str,
+    seed: u64,
+    mirrored: bool,
+    rounds: usize,
+    area_a: usize,
+    area_b: usize,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct Standing {
+    genome: String,
+    rating: f64,
+    games: usize,
+    wins: usize,
+    losses: usize,
+    draws: usize,
+    mean_area: f64,
+}
+
+impl Standing {
+    fn record(&mut self, area: usize, outcome: Ordering) {
+        self.games += 1;
+        match outcome {
+            Ordering::Greater => self.wins += 1,
+            Ordering::Less => self.losses += 1,
+            Ordering::Equal => self.draws += 1,
+        }
+        self.mean_area += (area as f64 - self.mean_area) / self.games as f64;
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct TournamentResult {
+    standings: Vec<Standing>,
+}
+
+// Plays every pair of entrants against each other on the same seeds, once from each starting
+// position, and rates them by the area their lineages hold at the end of each game
+pub fn run() -> Result<()> {
+    let config: Config = config_file::load("tournament")?;
+    if config.entrants.len() < 2 {
+        bail!("A tournament needs at least two entrants");
+    }
+
+    let genomes = Libraries::default().genomes(&config.entrants)?;
+    let positions = config.positions.unwrap_or([
+        Position::new(config.x_size / 4, config.y_size / 2),
+        Position::new(3 * config.x_size / 4, config.y_size / 2),
+    ]);
+
+    let mut standings = config
+        .entrants
+        .iter()
+        .map(|reference| Standing {
+            genome: reference.clone(),
+            rating: INITIAL_RATING,
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    let mut games = csv::Writer::from_path("tournament_games.csv")?;
+
+    for a in 0..genomes.len() {
+        for b in a + 1..genomes.len() {
+            let (mut wins, mut losses, mut draws) = (0, 0, 0);
+            for game in 0..config.games {
+                let seed = config.rng_seed + game as u64;
+                for mirrored in [false, true] {
+                    // The mirrored game hands b both the start position and the place in the
+                    // update order that a had, since plants are not updated simultaneously
+                    let (rounds, area_a, area_b) = if mirrored {
+                        let (rounds, area_b, area_a) =
+                            play(&config, [&genomes[b], &genomes[a]], positions, seed)?;
+                        (rounds, area_a, area_b)
+                    } else {
+                        play(&config, [&genomes[a], &genomes[b]], positions, seed)?
+                    };
+                    let outcome = area_a.cmp(&area_b);
+                    match outcome {
+                        Ordering::Greater => wins += 1,
+                        Ordering::Less => losses += 1,
+                        Ordering::Equal => draws += 1,
+                    }
+                    standings[a].record(area_a, outcome);
+                    standings[b].record(area_b, outcome.reverse());
+                    update_ratings(&mut standings, a, b, outcome, config.k_factor);
+                    games.serialize(Game {
+                        a: &config.entrants[a],
+                        b: &config.entrants[b],
+                        seed,
+                        mirrored,
+                        rounds,
+                        area_a,
+                        area_b,
+                    })?;
+                }
+            }
+            games.flush()?;
+            let (name_a, name_b) = (&config.entrants[a], &config.entrants[b]);
+            println!("{name_a} vs {name_b}: {wins} wins, {losses} losses, {draws} draws");
+        }
+    }
+
+    standings.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+    for standing in &standings {
+        println!("{:>7.1} {}", standing.rating, standing.genome);
+    }
+    let toml = toml::to_string_pretty(&TournamentResult { standings })?;
+    std::fs::write("tournament_result.toml", toml)?;
+    Ok(())
+}
+
+// Runs a single game until one lineage is left or time runs out, returning the number of rounds
+// played and the final areas of both lineages
+fn play(
+    config: &Config,
+    genomes: [&GenomeKind; 2],
+    positions: [Position; 2],
+    seed: u64,
+) -> Result<(usize, usize, usize)> {
+    let mut rng = Rng::from_seed(seed);
+    let mut world = WorldBuilder::new(config.x_size, config.y_size);
+    world
+        .seed_rate(config.seed_rate)
+        .mutation_rate(config.mutation_rate)
+        .mutation(config.mutation);
+    for (genome, position) in genomes.into_iter().zip(positions) {
+        world.add_plant(genome.clone(), PlantLabel::default(), position)?;
+    }
+    let mut world = world.build();
+
+    let mut rounds = 0;
+    let mut areas = world.founder_areas();
+    while rounds < config.max_steps && areas.len() == 2 {
+        world.step(rounds, &mut rng);
+        areas = world.founder_areas();
+        rounds += 1;
+    }
+    let area = |founder_id: usize| areas.get(&GenomeId::from(founder_id)).copied().unwrap_or(0);
+    Ok((rounds, area(0), area(1)))
+}
+
+fn update_ratings(standings: &mut [Standing], a: usize, b: usize, outcome: Ordering, k: f64) {
+    let score = match outcome {
+        Ordering::Greater => 1.0,
+        Ordering::Less => 0.0,
+        Ordering::Equal => 0.5,
+    };
+    let expected = 1.0 / (1.0 + 10f64.powf((standings[b].rating - standings[a].rating) / 400.0));
+    let delta = k * (score - expected);
+    standings[a].rating += delta;
+    standings[b].rating -= delta;
+}
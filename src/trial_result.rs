@@ -1,10 +1,57 @@
 use crate::active_genome::ActiveGenome;
-use crate::either::Either;
+use crate::either::Either::{self, *};
+use crate::fitness::Fitness;
+use crate::genome::GenomeKind;
 use crate::inactive_genome::InactiveGenome;
+use crate::morphology::MorphologySummary;
+use crate::plant_label::PlantLabel;
+use anyhow::{Context, Result};
 use derive_more::Constructor;
-use serde::Serialize;
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::Path;
 
-#[derive(Debug, Constructor, Serialize)]
+#[derive(Debug, Constructor, CopyGetters, Serialize, Deserialize)]
+pub struct TopGenome<'a> {
+    #[getset(get_copy = "pub")]
+    fitness: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[getset(get_copy = "pub")]
+    morphology: Option<MorphologySummary>,
+    #[serde(flatten)]
+    genome: Cow<'a, Either<ActiveGenome, InactiveGenome>>,
+}
+
+impl TopGenome<'_> {
+    pub fn genome(&self) -> &GenomeKind {
+        match &*self.genome {
+            Living(active_genome) => active_genome.genome(),
+            Dead(inactive_genome) => inactive_genome.genome(),
+        }
+    }
+
+    pub fn label(&self) -> &PlantLabel {
+        match &*self.genome {
+            Living(active_genome) => active_genome.founder().label(),
+            Dead(inactive_genome) => inactive_genome.founder().label(),
+        }
+    }
+}
+
+#[derive(Debug, Constructor, CopyGetters, Getters, Serialize, Deserialize)]
 pub struct TrialResult<'a> {
-    top_genomes: Vec<&'a Either<ActiveGenome, InactiveGenome>>,
+    #[getset(get_copy = "pub")]
+    fitness: Fitness,
+    #[getset(get = "pub")]
+    top_genomes: Vec<TopGenome<'a>>,
+}
+
+impl TrialResult<'static> {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read trial result {}", path.display()))?;
+        toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse trial result {}", path.display()))
+    }
 }
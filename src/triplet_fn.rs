@@ -42,6 +42,8 @@ pub struct TripletFn {
 }
 
 impl TripletFn {
+    pub const LEN: usize = 18;
+
     pub fn from_fn(mut f: impl FnMut() -> f32) -> Self {
         Self {
             triplet_eee: f(),
@@ -134,6 +136,29 @@ impl TripletFn {
         }
     }
 
+    pub fn values(&self) -> [f32; Self::LEN] {
+        [
+            self.triplet_eee,
+            self.triplet_ees,
+            self.triplet_eeo,
+            self.triplet_ese,
+            self.triplet_ess,
+            self.triplet_eso,
+            self.triplet_eoe,
+            self.triplet_eos,
+            self.triplet_eoo,
+            self.triplet_oee,
+            self.triplet_oes,
+            self.triplet_oeo,
+            self.triplet_ose,
+            self.triplet_oss,
+            self.triplet_oso,
+            self.triplet_ooe,
+            self.triplet_oos,
+            self.triplet_ooo,
+        ]
+    }
+
     pub fn min(&self) -> f32 {
         self.triplet_eee
             .min(self.triplet_ees)
@@ -0,0 +1,27 @@
+use crate::live_server::LiveServer;
+use crate::terminal_viewer::TerminalViewer;
+use crate::world::World;
+use anyhow::Result;
+use enum_dispatch::enum_dispatch;
+
+#[enum_dispatch]
+pub enum ViewerKind {
+    TerminalViewer,
+    LiveServer,
+}
+
+// Follows a running world, getting a look at the board after every round
+#[enum_dispatch(ViewerKind)]
+pub trait Viewer {
+    // Returns false once the user asks to stop the run
+    fn update(&mut self, world: &World, round: usize) -> Result<bool>;
+
+    fn finish(&mut self, _world: &World, _round: usize) -> Result<()> {
+        Ok(())
+    }
+
+    // Keeps the viewer going on the final board after the run ended, until interrupted
+    fn linger(&mut self, _world: &World) -> Result<()> {
+        Ok(())
+    }
+}
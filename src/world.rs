@@ -1,61 +1,112 @@
+use crate::archive::{Archive, Descriptors, Elite};
 use crate::cell_kind::CellKind;
+use crate::diversity::Diversity;
+use crate::either::Either;
 use crate::entity::Entity;
-use crate::genome::GenomeKind;
+use crate::fitness::Fitness;
+use crate::founder::Founder;
+use crate::genome::{Genome, GenomeKind};
 use crate::genomes::GenomeId;
 use crate::grid::Grid;
+use crate::heatmap::{Heatmap, TileScore};
+use crate::inspection::{GenomeReport, LivingPlantReport, PlantReport, Report, Target, TileReport};
+use crate::morphology::MorphologySummary;
+use crate::mutation::Mutation;
 use crate::organisms::Organisms;
+use crate::phylogeny::Phylogeny;
+use crate::plant_label::PlantLabel;
 use crate::plants::PlantId;
+use crate::position::Position;
 use crate::rand::Rng;
+use crate::snapshot::Snapshot;
 use crate::tiles::TileId;
-use crate::trial_result;
-use std::io::Write;
+use crate::time_series::RoundStats;
+use crate::trial_result::{TopGenome, TrialResult};
+use crate::viewer::{Viewer, ViewerKind};
+use ahash::AHashMap;
+use anyhow::{bail, Result};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, LineWriter, Write};
+use std::path::Path;
+
+// The files a run keeps appending to, one row or snapshot at a time
+#[derive(Debug)]
+pub struct RunOutputs {
+    data: LineWriter<File>,
+    snapshots: BufWriter<File>,
+    time_series: csv::Writer<File>,
+    diversity: csv::Writer<File>,
+}
 
 #[derive(Debug, Clone)]
 pub struct World {
     take_top: usize,
     seed_rate: f32,
     mutation_rate: f32,
+    mutation: Mutation,
+    fitness: Fitness,
     grid: Grid,
     organisms: Organisms,
+    archive: Option<Archive>,
 }
 
 impl World {
-    pub fn new(grid: Grid, take_top: usize, seed_rate: f32, mutation_rate: f32) -> Self {
+    pub fn new(
+        grid: Grid,
+        take_top: usize,
+        seed_rate: f32,
+        mutation_rate: f32,
+        mutation: Mutation,
+        fitness: Fitness,
+    ) -> Self {
         let organisms = Organisms::default();
         World {
             take_top,
             seed_rate,
             mutation_rate,
+            mutation,
+            fitness,
             grid,
             organisms,
+            archive: None,
         }
     }
 
-    pub fn run(&mut self, rng: &mut Rng, max_rounds: usize, snapshot_interval: usize) {
-        let file = std::fs::File::create("data.js").unwrap();
-        let mut file = std::io::LineWriter::new(file);
+    pub fn set_archive(&mut self, archive: Archive) {
+        self.archive = Some(archive);
+    }
 
-        let x = self.grid.x_size();
-        let y = self.grid.y_size();
-        writeln!(file, "const board = {{ x_size: {x}, y_size: {y} }};").unwrap();
-        writeln!(file, "let tile_snapshots = [];").unwrap();
-        Self::write_snapshot(&mut file, self.tile_snapshot());
+    pub fn run(
+        &mut self,
+        rng: &mut Rng,
+        max_rounds: usize,
+        snapshot_interval: usize,
+        mut viewer: Option<&mut ViewerKind>,
+    ) {
+        // Console output would scramble the terminal viewer
+        let mut quiet = matches!(viewer.as_deref(), Some(ViewerKind::TerminalViewer(_)));
+        let mut outputs = self.create_outputs(Path::new("."));
 
         let mut tile_count = 0;
         while tile_count < max_rounds {
-            let plant_ids = self.organisms.active_plants().to_owned();
-            plant_ids.into_iter().rev().for_each(|plant_id| {
-                let plant = self.organisms.plant(plant_id);
-                match plant.energy_points(&self.grid) {
-                    energy_points if energy_points > 0 => {
-                        self.grow_plant(plant_id, energy_points, rng)
-                    }
-                    _ => self.remove_plant(plant_id, tile_count, rng),
+            let mean_energy = self.step(tile_count, rng);
+            self.record_round(&mut outputs, tile_count, mean_energy);
+
+            for founder in self.organisms.take_extinctions() {
+                let label = founder.label();
+                if *label != PlantLabel::default() && !quiet {
+                    let id = founder.id();
+                    println!("Lineage of genome {id}{label} went extinct in round {tile_count}");
                 }
-            });
+            }
 
             let plant_ids = self.organisms.active_plants();
             if plant_ids.len() < 2 {
+                if let Some(viewer) = viewer.take() {
+                    self.finish_viewer(viewer, tile_count);
+                }
                 if plant_ids.is_empty() {
                     println!("No plants survived");
                 } else {
@@ -64,17 +115,399 @@ impl World {
                 break;
             }
 
+            if let Some(active_viewer) = viewer.as_deref_mut() {
+                match active_viewer.update(self, tile_count) {
+                    Ok(true) => {}
+                    Ok(false) => break,
+                    // A broken viewer should not cost the results of the run
+                    Err(error) => {
+                        self.finish_viewer(active_viewer, tile_count);
+                        eprintln!(
+                            "Viewer failed in round {tile_count}, continuing without it: {error:#}"
+                        );
+                        viewer = None;
+                        quiet = false;
+                    }
+                }
+            }
+
             if tile_count % snapshot_interval == 0 {
-                println!("round: {}", tile_count);
-                Self::write_snapshot(&mut file, self.tile_snapshot());
+                if !quiet {
+                    println!("round: {}", tile_count);
+                }
+                self.record_snapshot(&mut outputs, tile_count);
+                self.update_archive(tile_count);
             }
             tile_count += 1;
         }
 
-        let top_genomes = self.organisms.top_genomes(self.take_top);
-        let trial_result = trial_result::TrialResult::new(top_genomes);
+        if let Some(viewer) = viewer {
+            self.finish_viewer(viewer, tile_count);
+        }
+        self.write_results(Path::new("."), tile_count);
+        self.update_archive(tile_count);
+        if let Some(archive) = &self.archive {
+            archive.save().unwrap();
+            println!("Archive holds {} elites", archive.len());
+        }
+    }
+
+    // Opens the files a run writes round by round in `dir`, starting them off with the board
+    // before the first round
+    pub fn create_outputs(&self, dir: &Path) -> RunOutputs {
+        let file = File::create(dir.join("data.js")).unwrap();
+        let mut data = LineWriter::new(file);
+
+        let x = self.grid.x_size();
+        let y = self.grid.y_size();
+        writeln!(data, "const board = {{ x_size: {x}, y_size: {y} }};").unwrap();
+        writeln!(data, "const founder_labels = {{").unwrap();
+        for &genome_id in self.organisms.active_genomes() {
+            let founder = self.organisms.genome(genome_id).founder();
+            if *founder.label() != PlantLabel::default() {
+                let id = usize::from(founder.id()) + 1;
+                let label = serde_json::to_string(founder.label()).unwrap();
+                writeln!(data, "  {id}: {label},").unwrap();
+            }
+        }
+        writeln!(data, "}};").unwrap();
+        writeln!(data, "let tile_snapshots = [];").unwrap();
+        writeln!(data, "let founder_snapshots = [];").unwrap();
+        let snapshots = File::create(dir.join("snapshots.jsonl")).unwrap();
+        let snapshots = BufWriter::new(snapshots);
+        // The board before the first round only goes to data.js, as snapshots.jsonl is keyed by
+        // the round each board was left in
+        self.write_snapshots(&mut data);
+
+        RunOutputs {
+            data,
+            snapshots,
+            time_series: csv::Writer::from_path(dir.join("time_series.csv")).unwrap(),
+            diversity: csv::Writer::from_path(dir.join("diversity.csv")).unwrap(),
+        }
+    }
+
+    pub fn record_round(&mut self, outputs: &mut RunOutputs, round: usize, mean_energy: f64) {
+        outputs
+            .time_series
+            .serialize(self.round_stats(round, mean_energy))
+            .unwrap();
+    }
+
+    pub fn record_snapshot(&self, outputs: &mut RunOutputs, round: usize) {
+        self.write_snapshots(&mut outputs.data);
+        self.write_board_snapshot(&mut outputs.snapshots, round);
+        outputs.time_series.flush().unwrap();
+        outputs
+            .diversity
+            .serialize(Diversity::new(&self.organisms, round))
+            .unwrap();
+        outputs.diversity.flush().unwrap();
+    }
+
+    // Every living genome as a candidate elite, described by the average shape of its plants
+    pub fn elites(&self, fitness: Fitness, round: usize) -> Vec<Elite> {
+        let values = fitness.evaluate(self.organisms.genomes(), round, 0);
+        self.morphologies()
+            .into_iter()
+            .map(|(genome_id, morphology)| {
+                let active_genome = self.organisms.genome(genome_id);
+                let stats = active_genome.stats();
+                let descriptors = Descriptors::new(
+                    morphology.mean_compactness(),
+                    morphology.mean_perimeter_ratio(),
+                    stats.takeovers_performed() as f64 / stats.cells_grown().max(1) as f64,
+                );
+                let genome = active_genome.genome().clone();
+                Elite::new(values[usize::from(genome_id)], descriptors, genome)
+            })
+            .collect()
+    }
+
+    // Shape of every living plant summarized per genome
+    pub fn morphologies(&self) -> BTreeMap<GenomeId, MorphologySummary> {
+        let mut morphologies: BTreeMap<GenomeId, MorphologySummary> = BTreeMap::new();
+        self.organisms.active_plants().iter().for_each(|&plant_id| {
+            let active_plant = self.organisms.plant(plant_id);
+            let morphology = active_plant.morphology(&self.grid);
+            morphologies
+                .entry(active_plant.genome_id())
+                .or_default()
+                .add(&morphology);
+        });
+        morphologies
+    }
+
+    fn finish_viewer(&self, viewer: &mut ViewerKind, round: usize) {
+        if let Err(error) = viewer.finish(self, round) {
+            eprintln!("Viewer failed to finish: {error:#}");
+        }
+    }
+
+    pub fn take_archive(&mut self) -> Option<Archive> {
+        self.archive.take()
+    }
+
+    pub fn update_archive(&mut self, round: usize) {
+        let Some(fitness) = self.archive.as_ref().map(|archive| *archive.fitness()) else {
+            return;
+        };
+        let elites = self.elites(fitness, round);
+        let archive = self.archive.as_mut().unwrap();
+        elites.into_iter().for_each(|elite| {
+            archive.insert(elite);
+        });
+    }
+
+    // Writes the top genomes and the phylogeny of everything that ever lived into `dir`
+    pub fn write_results(&self, dir: &Path, round: usize) {
+        let morphologies = self.morphologies();
+        let top_genomes = self
+            .organisms
+            .top_genomes(self.take_top, self.fitness, round)
+            .into_iter()
+            .map(|(fitness, genome)| {
+                let morphology = match genome {
+                    Either::Living(active_genome) => morphologies.get(&active_genome.id()).copied(),
+                    Either::Dead(_) => None,
+                };
+                TopGenome::new(fitness, morphology, Cow::Borrowed(genome))
+            })
+            .collect();
+        let trial_result = TrialResult::new(self.fitness, top_genomes);
         let toml = toml::to_string_pretty(&trial_result).unwrap();
-        std::fs::write("trial_result.toml", toml).unwrap();
+        std::fs::write(dir.join("trial_result.toml"), toml).unwrap();
+
+        let phylogeny = Phylogeny::new(self.organisms.genomes());
+        std::fs::write(dir.join("phylogeny.nwk"), phylogeny.to_newick()).unwrap();
+        let json = serde_json::to_string(&phylogeny).unwrap();
+        std::fs::write(dir.join("phylogeny.json"), json).unwrap();
+    }
+
+    pub fn num_plants(&self) -> usize {
+        self.organisms.active_plants().len()
+    }
+
+    pub fn tile_at(&self, position: Position) -> TileId {
+        self.grid.id_at(position)
+    }
+
+    pub fn inspect(&self, target: Target, round: usize) -> Result<Report<'_>> {
+        let (tile, plant_id) = match target {
+            Target::Tile(tile_id) => {
+                if usize::from(tile_id) >= self.grid.size() {
+                    bail!("Tile {} is outside the board", usize::from(tile_id));
+                }
+                let (plant_id, cell_kind) = match self.grid.entity(tile_id) {
+                    Entity::Empty => (None, None),
+                    Entity::Cell(plant_id, cell_kind) => (Some(plant_id), Some(cell_kind)),
+                };
+                let position = self.grid.position(tile_id);
+                let tile = TileReport::new(usize::from(tile_id), position, cell_kind);
+                (Some(tile), plant_id)
+            }
+            Target::Plant(plant_id) => {
+                if usize::from(plant_id) >= self.organisms.plants().len() {
+                    bail!("Plant {plant_id} does not exist");
+                }
+                (None, Some(plant_id))
+            }
+            Target::Genome(_) => (None, None),
+        };
+
+        let plant = plant_id.map(|plant_id| match &self.organisms.plants()[plant_id] {
+            Either::Living(active_plant) => {
+                let energy_yield = active_plant.energy_yield(&self.grid);
+                let living = LivingPlantReport::new(
+                    active_plant.created_at(),
+                    round - active_plant.created_at(),
+                    active_plant.size(),
+                    energy_yield,
+                    active_plant.energy_usage(&self.grid),
+                    active_plant.energy_points(energy_yield, &self.grid),
+                    active_plant.morphology(&self.grid),
+                );
+                PlantReport::new(plant_id, active_plant.genome_id(), Some(living))
+            }
+            Either::Dead(inactive_plant) => {
+                PlantReport::new(plant_id, inactive_plant.genome_id(), None)
+            }
+        });
+
+        let genome_id = match target {
+            Target::Genome(genome_id) => Some(genome_id),
+            _ => plant.as_ref().map(|plant| plant.genome_id()),
+        };
+        let genome = match genome_id {
+            Some(genome_id) if usize::from(genome_id) >= self.organisms.genomes().len() => {
+                bail!("Genome {genome_id} does not exist")
+            }
+            Some(genome_id) => {
+                let genomes = self.organisms.genomes();
+                let parent = |genome_id: GenomeId| match &genomes[genome_id] {
+                    Either::Living(active_genome) => active_genome.parent_genome_id(),
+                    Either::Dead(inactive_genome) => inactive_genome.parent_genome_id(),
+                };
+                let lineage = std::iter::successors(parent(genome_id), |&id| parent(id)).collect();
+                let created_at = match &genomes[genome_id] {
+                    Either::Living(active_genome) => active_genome.created_at(),
+                    Either::Dead(inactive_genome) => inactive_genome.created_at(),
+                };
+                Some(GenomeReport::new(
+                    round - created_at,
+                    lineage,
+                    &genomes[genome_id],
+                ))
+            }
+            None => None,
+        };
+
+        Ok(Report::new(round, tile, plant, genome))
+    }
+
+    // Where a living plant would grow next round, taking its current energy points but at
+    // least one so that starving plants still show their preferences, and the age it will have
+    // in that round
+    pub fn heatmap(&self, plant_id: PlantId, round: usize) -> Result<Heatmap> {
+        if usize::from(plant_id) >= self.organisms.plants().len()
+            || self.organisms.plants()[plant_id].is_dead()
+        {
+            bail!("Plant {plant_id} is not alive");
+        }
+        let active_plant = self.organisms.plant(plant_id);
+        let genome_id = active_plant.genome_id();
+        let energy_yield = active_plant.energy_yield(&self.grid);
+        let points = active_plant.energy_points(energy_yield, &self.grid).max(1);
+        let weights = self
+            .organisms
+            .tile_weights(plant_id, &self.grid, points, round + 1);
+
+        let total_weight = weights
+            .iter()
+            .map(|&(_, _, weight)| weight as f64)
+            .sum::<f64>();
+        let tiles = weights
+            .into_iter()
+            .map(|(tile_id, score, weight)| {
+                TileScore::new(
+                    usize::from(tile_id),
+                    self.grid.position(tile_id),
+                    score,
+                    weight as f64 / total_weight,
+                )
+            })
+            .collect();
+        let score_weight = self.organisms.genome(genome_id).genome().score_weight();
+        Ok(Heatmap::new(
+            round,
+            plant_id,
+            genome_id,
+            points,
+            score_weight,
+            tiles,
+        ))
+    }
+
+    // Living genome with the highest yield of a single plant
+    pub fn top_yield(&self) -> Option<(GenomeId, usize)> {
+        self.organisms
+            .active_genomes()
+            .iter()
+            .map(|&genome_id| (genome_id, self.organisms.genome(genome_id).max_yield()))
+            .max_by_key(|&(_, max_yield)| max_yield)
+    }
+
+    pub fn num_genomes(&self) -> usize {
+        self.organisms.active_genomes().len()
+    }
+
+    // The fittest living genomes, ready to be sent to another world
+    pub fn emigrants(&self, n: usize, round: usize) -> Vec<(GenomeId, Founder, GenomeKind)> {
+        self.organisms
+            .top_living_genomes(n, self.fitness, round)
+            .into_iter()
+            .map(|active_genome| {
+                let genome = active_genome.genome().clone();
+                (active_genome.id(), active_genome.founder().clone(), genome)
+            })
+            .collect()
+    }
+
+    // Seeds a new founder on a random empty tile, returning its genome id or none if the world
+    // is full
+    pub fn immigrate(
+        &mut self,
+        genome: GenomeKind,
+        label: PlantLabel,
+        round: usize,
+        rng: &mut Rng,
+    ) -> Option<GenomeId> {
+        let empty_tiles = self.grid.empty_tiles();
+        if empty_tiles.is_empty() {
+            return None;
+        }
+        let tile_id = empty_tiles[rng.uniform(empty_tiles.len())];
+        let genome_id = self.organisms.add_founder(genome, label, round);
+        let plant_id = self.organisms.add_plant(genome_id, round);
+        self.replace_entity(tile_id, Entity::Cell(plant_id, CellKind::Branch));
+        Some(genome_id)
+    }
+
+    // Harvests and grows every plant once, returning the mean energy per plant
+    pub fn step(&mut self, round: usize, rng: &mut Rng) -> f64 {
+        let plant_ids = self.organisms.active_plants().to_owned();
+        let num_plants = plant_ids.len();
+        let mut total_energy = 0;
+        plant_ids.into_iter().rev().for_each(|plant_id| {
+            match self.organisms.harvest(plant_id, &self.grid) {
+                energy_points if energy_points > 0 => {
+                    total_energy += energy_points;
+                    self.grow_plant(plant_id, energy_points, round, rng)
+                }
+                _ => self.remove_plant(plant_id, round, rng),
+            }
+        });
+        total_energy as f64 / num_plants.max(1) as f64
+    }
+
+    // Number of cells held by the descendants of each surviving founder
+    pub fn founder_areas(&self) -> AHashMap<GenomeId, usize> {
+        let mut areas = AHashMap::new();
+        self.organisms.active_plants().iter().for_each(|&plant_id| {
+            let active_plant = self.organisms.plant(plant_id);
+            let founder_id = self
+                .organisms
+                .genome(active_plant.genome_id())
+                .founder()
+                .id();
+            *areas.entry(founder_id).or_default() += active_plant.size();
+        });
+        areas
+    }
+
+    fn round_stats(&mut self, round: usize, mean_energy: f64) -> RoundStats {
+        let sizes = self
+            .organisms
+            .active_plants()
+            .iter()
+            .map(|&plant_id| self.organisms.plant(plant_id).size())
+            .collect::<Vec<_>>();
+        let occupied = sizes.iter().sum::<usize>();
+        let mean_size = occupied as f64 / sizes.len().max(1) as f64;
+        let max_size = sizes.iter().copied().max().unwrap_or(0);
+        let events = self.organisms.take_events();
+        RoundStats {
+            round,
+            plants: sizes.len(),
+            genomes: self.organisms.active_genomes().len(),
+            occupied_fraction: occupied as f64 / self.grid.size() as f64,
+            mean_size,
+            max_size,
+            mean_energy,
+            births: events.births,
+            deaths: events.deaths,
+            mutations: events.mutations,
+        }
     }
 
     fn replace_entity(&mut self, tile_id: TileId, new_entity: Entity) -> Entity {
@@ -84,6 +517,10 @@ impl World {
         if old_entity != new_entity {
             if let Entity::Cell(plant_id, _) = new_entity {
                 self.organisms.occupy(plant_id, tile_id, &self.grid);
+
+                if let Entity::Cell(old_plant_id, _) = old_entity {
+                    self.organisms.takeover(plant_id, old_plant_id);
+                }
             }
 
             if let Entity::Cell(old_plant_id, _) = old_entity {
@@ -108,9 +545,10 @@ impl World {
             if rng.sample() < self.seed_rate {
                 // Should create a new genome?
                 let plant_id = if rng.sample() < self.mutation_rate {
-                    self.organisms.add_mutated_plant(genome_id, round, rng)
+                    self.organisms
+                        .add_mutated_plant(genome_id, round, &self.mutation, rng)
                 } else {
-                    self.organisms.add_plant(genome_id)
+                    self.organisms.add_plant(genome_id, round)
                 };
                 self.replace_entity(tile_id, Entity::Cell(plant_id, CellKind::Branch));
             }
@@ -118,12 +556,12 @@ impl World {
         self.organisms.remove_plant(plant_id, round);
     }
 
-    fn grow_plant(&mut self, plant_id: PlantId, energy_points: usize, rng: &mut Rng) {
+    fn grow_plant(&mut self, plant_id: PlantId, energy_points: usize, round: usize, rng: &mut Rng) {
         let mut energy_points = energy_points;
 
         while let Some(tile_id) =
             self.organisms
-                .choose_tile(plant_id, &self.grid, energy_points, rng)
+                .choose_tile(plant_id, &self.grid, energy_points, round, rng)
         {
             let old_entity = self.replace_entity(tile_id, Entity::Cell(plant_id, CellKind::Branch));
             if old_entity == Entity::Empty {
@@ -144,17 +582,67 @@ impl World {
         });
     }
 
-    pub fn add_genome(&mut self, genome: GenomeKind) -> GenomeId {
-        self.organisms.add_genome(genome, None, 0)
+    pub fn add_genome(&mut self, genome: GenomeKind, label: PlantLabel) -> GenomeId {
+        self.organisms.add_founder(genome, label, 0)
+    }
+
+    pub fn add_plant(&mut self, genome_id: GenomeId, tile_ids: &[TileId]) {
+        let new_plant_id = self.organisms.add_plant(genome_id, 0);
+        tile_ids.iter().for_each(|&tile_id| {
+            self.replace_entity(tile_id, Entity::Cell(new_plant_id, CellKind::Branch));
+        });
+    }
+
+    fn write_snapshots(&self, writer: &mut impl Write) {
+        let tile_snapshot = self.snapshot(|plant_id| usize::from(plant_id) + 1);
+        Self::write_snapshot(writer, "tile_snapshots", tile_snapshot);
+        let founder_snapshot = self.snapshot(|plant_id| {
+            let genome_id = self.organisms.plant(plant_id).genome_id();
+            usize::from(self.organisms.genome(genome_id).founder().id()) + 1
+        });
+        Self::write_snapshot(writer, "founder_snapshots", founder_snapshot);
+    }
+
+    fn write_board_snapshot(&self, snapshots: &mut impl Write, round: usize) {
+        serde_json::to_writer(&mut *snapshots, &self.board_snapshot(round)).unwrap();
+        writeln!(snapshots).unwrap();
+        snapshots.flush().unwrap();
     }
 
-    pub fn add_plant(&mut self, genome_id: GenomeId, tile_id: TileId) {
-        let new_plant_id = self.organisms.add_plant(genome_id);
-        self.replace_entity(tile_id, Entity::Cell(new_plant_id, CellKind::Branch));
+    pub fn board_snapshot(&self, round: usize) -> Snapshot {
+        let mut plants = Vec::with_capacity(self.grid.size());
+        let mut kinds = Vec::with_capacity(self.grid.size());
+        let mut genomes = BTreeMap::new();
+        let mut founders = BTreeMap::new();
+        for &entity in self.grid.columns().flatten() {
+            match entity {
+                Entity::Empty => {
+                    plants.push(0);
+                    kinds.push(None);
+                }
+                Entity::Cell(plant_id, cell_kind) => {
+                    let genome_id = self.organisms.plant(plant_id).genome_id();
+                    let founder_id = self.organisms.genome(genome_id).founder().id();
+                    plants.push(usize::from(plant_id) + 1);
+                    kinds.push(Some(cell_kind));
+                    genomes.insert(usize::from(plant_id) + 1, usize::from(genome_id));
+                    founders.insert(usize::from(genome_id), usize::from(founder_id));
+                }
+            }
+        }
+        Snapshot::new(
+            round,
+            self.grid.x_size(),
+            self.grid.y_size(),
+            plants,
+            kinds,
+            genomes,
+            founders,
+        )
     }
 
-    fn write_snapshot(writer: &mut impl Write, snapshot: Vec<Vec<usize>>) {
-        writeln!(writer, "tile_snapshots.push([").unwrap();
+    fn write_snapshot(writer: &mut impl Write, name: &str, snapshot: Vec<Vec<usize>>) {
+        writeln!(writer, "{name}.push([").unwrap();
         for row in snapshot {
             writeln!(
                 writer,
@@ -169,7 +657,7 @@ impl World {
         writeln!(writer, "]);").unwrap();
     }
 
-    fn tile_snapshot(&self) -> Vec<Vec<usize>> {
+    fn snapshot(&self, f: impl Fn(PlantId) -> usize) -> Vec<Vec<usize>> {
         self.grid
             .columns()
             .map(|chunk| {
@@ -177,7 +665,7 @@ impl World {
                     .iter()
                     .map(|&entity| match entity {
                         Entity::Empty => 0,
-                        Entity::Cell(plant_id, _) => usize::from(plant_id) + 1,
+                        Entity::Cell(plant_id, _) => f(plant_id),
                     })
                     .collect()
             })
@@ -1,9 +1,17 @@
+use crate::archive::Archive;
+use crate::fitness::Fitness;
 use crate::genome::GenomeKind;
-use crate::genomes::{DoubletGenome, TripletGenome};
+use crate::genomes::{
+    BlobGenome, DoubletGenome, GenomeId, NeuralGenome, OrientedGenome, TripletGenome,
+};
 use crate::grid::Grid;
+use crate::mutation::Mutation;
+use crate::placement::Placement;
+use crate::plant_label::PlantLabel;
 use crate::position::Position;
 use crate::rand::Rng;
 use crate::square_grid::SquareGrid;
+use crate::territory::Territory;
 use crate::tiles::TileId;
 use crate::world::World;
 use anyhow::Result;
@@ -15,8 +23,12 @@ pub struct WorldBuilder {
     take_top: usize,
     seed_rate: f32,
     mutation_rate: f32,
-    plants: Vec<(GenomeKind, TileId)>,
+    mutation: Mutation,
+    fitness: Fitness,
+    // Each founder genome with the cells of every plant it starts out with
+    founders: Vec<(GenomeKind, PlantLabel, Vec<Vec<TileId>>)>,
     unused_tiles: IntSet<TileId>,
+    archive: Option<Archive>,
 }
 
 impl WorldBuilder {
@@ -45,10 +57,30 @@ impl WorldBuilder {
         self
     }
 
-    pub fn add_plant(&mut self, genome: GenomeKind, cell_position: Position) -> Result<&mut Self> {
+    pub fn mutation(&mut self, mutation: Mutation) -> &mut Self {
+        self.mutation = mutation;
+        self
+    }
+
+    pub fn fitness(&mut self, fitness: Fitness) -> &mut Self {
+        self.fitness = fitness;
+        self
+    }
+
+    pub fn archive(&mut self, archive: Archive) -> &mut Self {
+        self.archive = Some(archive);
+        self
+    }
+
+    pub fn add_plant(
+        &mut self,
+        genome: GenomeKind,
+        label: PlantLabel,
+        cell_position: Position,
+    ) -> Result<&mut Self> {
         let tile_id = self.grid.id_at(cell_position);
         if self.unused_tiles.remove(&tile_id) {
-            self.plants.push((genome, tile_id));
+            self.founders.push((genome, label, vec![vec![tile_id]]));
             Ok(self)
         } else {
             Err(anyhow::anyhow!("Tile already occupied: {cell_position:?}"))
@@ -59,12 +91,17 @@ impl WorldBuilder {
         &mut self,
         kind: &str,
         total: usize,
+        label: PlantLabel,
+        placement: &Placement,
         rng: &mut Rng,
     ) -> Result<&mut Self> {
         let random_genomes = (0..total)
             .map(|_| match kind {
                 "doublet_genome" => Ok(DoubletGenome::random(rng).into()),
                 "triplet_genome" => Ok(TripletGenome::random(rng).into()),
+                "neural_genome" => Ok(NeuralGenome::random(rng).into()),
+                "blob_genome" => Ok(BlobGenome::random(rng).into()),
+                "oriented_genome" => Ok(OrientedGenome::random(rng).into()),
                 _ => Err(anyhow::anyhow!("Unknown plant kind: {}", kind)),
             })
             .collect::<Result<Vec<GenomeKind>>>()?;
@@ -73,26 +110,85 @@ impl WorldBuilder {
             anyhow::bail!("Not enough empty tiles to place {total} random {kind} plants");
         }
 
-        let mut empty_tiles = self.unused_tiles.iter().copied().collect::<Vec<_>>();
-        rng.shuffle(&mut empty_tiles);
-        random_genomes
+        let plants = random_genomes
+            .into_iter()
+            .map(|genome| (genome, label.clone()))
+            .collect();
+        self.add_placed_plants(plants, placement, rng)
+    }
+
+    pub fn add_placed_plants(
+        &mut self,
+        plants: Vec<(GenomeKind, PlantLabel)>,
+        placement: &Placement,
+        rng: &mut Rng,
+    ) -> Result<&mut Self> {
+        let tile_ids = placement.tiles(plants.len(), &self.grid, &self.unused_tiles, rng)?;
+        plants
             .into_iter()
-            .zip(empty_tiles)
-            .for_each(|(genome, tile_id)| {
+            .zip(tile_ids)
+            .for_each(|((genome, label), tile_id)| {
                 self.unused_tiles.remove(&tile_id);
-                self.plants.push((genome, tile_id));
+                self.founders.push((genome, label, vec![vec![tile_id]]));
             });
 
         Ok(self)
     }
 
+    // Fills the territory with plants of a single founder genome, skipping patches that overlap
+    // tiles already taken so territories can be added around earlier seeds
+    // Returns the id the territory's genome gets in the built world
+    pub fn add_territory(
+        &mut self,
+        genome: GenomeKind,
+        label: PlantLabel,
+        territory: &Territory,
+    ) -> Result<GenomeId> {
+        let patches = territory
+            .patches(&self.grid)?
+            .into_iter()
+            .filter(|patch| {
+                patch
+                    .iter()
+                    .all(|tile_id| self.unused_tiles.contains(tile_id))
+            })
+            .collect::<Vec<_>>();
+        if patches.is_empty() {
+            anyhow::bail!("No room left for territory {territory:?}");
+        }
+
+        patches.iter().flatten().for_each(|tile_id| {
+            self.unused_tiles.remove(tile_id);
+        });
+        let genome_id = GenomeId::from(self.founders.len());
+        self.founders.push((genome, label, patches));
+        Ok(genome_id)
+    }
+
     pub fn build(self) -> World {
         let grid = Grid::new(self.grid);
-        let mut world = World::new(grid, self.take_top, self.seed_rate, self.mutation_rate);
-        self.plants.into_iter().for_each(|(genome, tile_id)| {
-            let genome_id = world.add_genome(genome);
-            world.add_plant(genome_id, tile_id);
-        });
+        let mut world = World::new(
+            grid,
+            self.take_top,
+            self.seed_rate,
+            self.mutation_rate,
+            self.mutation,
+            self.fitness,
+        );
+        // Founders get their ids in the order they were added
+        self.founders
+            .into_iter()
+            .enumerate()
+            .for_each(|(idx, (genome, label, plants))| {
+                let genome_id = world.add_genome(genome, label);
+                assert_eq!(genome_id, GenomeId::from(idx));
+                plants
+                    .iter()
+                    .for_each(|tile_ids| world.add_plant(genome_id, tile_ids));
+            });
+        if let Some(archive) = self.archive {
+            world.set_archive(archive);
+        }
         world
     }
 }